@@ -0,0 +1,361 @@
+use std::{cmp::Ordering, fmt, time::SystemTime};
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::version::VersionError;
+
+const SEPARATORS: &[char] = &['.', '-', '_'];
+
+/// The format used when none is given.
+pub const DEFAULT_FORMAT: &str = "YYYY.0M.MICRO";
+
+/// A segment of a CalVer format, as defined on calver.org.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum::Display, strum::EnumString,
+)]
+pub enum Token {
+    #[strum(serialize = "YYYY")]
+    FullYear,
+    #[strum(serialize = "YY")]
+    ShortYear,
+    #[strum(serialize = "0Y")]
+    PaddedYear,
+    #[strum(serialize = "MM")]
+    Month,
+    #[strum(serialize = "0M")]
+    PaddedMonth,
+    #[strum(serialize = "WW")]
+    Week,
+    #[strum(serialize = "0W")]
+    PaddedWeek,
+    #[strum(serialize = "DD")]
+    Day,
+    #[strum(serialize = "0D")]
+    PaddedDay,
+    #[strum(serialize = "MAJOR")]
+    Major,
+    #[strum(serialize = "MINOR")]
+    Minor,
+    #[strum(serialize = "MICRO")]
+    Micro,
+}
+
+impl Token {
+    fn is_date(self) -> bool {
+        !matches!(self, Token::Major | Token::Minor | Token::Micro)
+    }
+
+    fn is_padded(self) -> bool {
+        matches!(
+            self,
+            Token::PaddedYear | Token::PaddedMonth | Token::PaddedWeek | Token::PaddedDay
+        )
+    }
+
+    /// The name of the segment, as used to access it from nushell.
+    pub fn name(self) -> &'static str {
+        match self {
+            Token::FullYear | Token::ShortYear | Token::PaddedYear => "year",
+            Token::Month | Token::PaddedMonth => "month",
+            Token::Week | Token::PaddedWeek => "week",
+            Token::Day | Token::PaddedDay => "day",
+            Token::Major => "major",
+            Token::Minor => "minor",
+            Token::Micro => "micro",
+        }
+    }
+
+    /// The value of this segment for the given date.
+    fn for_date(self, date: &NaiveDate) -> u64 {
+        let value = match self {
+            Token::FullYear => date.year() as u32,
+            Token::ShortYear | Token::PaddedYear => date.year() as u32 - 2000,
+            Token::Month | Token::PaddedMonth => date.month(),
+            Token::Week | Token::PaddedWeek => date.ordinal0() / 7 + 1,
+            Token::Day | Token::PaddedDay => date.day(),
+            Token::Major | Token::Minor | Token::Micro => 0,
+        };
+        value.into()
+    }
+}
+
+/// A CalVer format, e.g. `YYYY.0M.MICRO`: a list of segments separated by `.`, `-` or `_`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalverFormat {
+    tokens: Vec<Token>,
+    /// The separators between the tokens, so there is one less separator than tokens.
+    separators: Vec<char>,
+}
+
+impl CalverFormat {
+    pub fn parse(format: &str) -> Result<Self, VersionError> {
+        let invalid = |reason: String| VersionError::InvalidCalverFormat(format.to_owned(), reason);
+
+        let mut tokens = vec![];
+        let mut separators = vec![];
+        let mut rest = format;
+        loop {
+            let (token, tail) = match rest.find(SEPARATORS) {
+                Some(i) => (&rest[..i], Some(&rest[i..])),
+                None => (rest, None),
+            };
+            let token: Token = token
+                .parse()
+                .map_err(|_| invalid(format!("unknown segment '{token}'")))?;
+            if tokens.iter().any(|t: &Token| t.name() == token.name()) {
+                return Err(invalid(format!("duplicate {} segment", token.name())));
+            }
+            tokens.push(token);
+            match tail.and_then(|t| t.chars().next().map(|c| (c, &t[1..]))) {
+                Some((separator, tail)) => {
+                    separators.push(separator);
+                    rest = tail;
+                }
+                None => break,
+            }
+        }
+
+        if !tokens.iter().any(|t| t.is_date()) {
+            return Err(invalid("the format has no date segment".to_string()));
+        }
+        Ok(Self { tokens, separators })
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.tokens.iter().position(|t| t.name() == name)
+    }
+}
+
+impl fmt::Display for CalverFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, token) in self.tokens.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", self.separators[i - 1])?;
+            }
+            write!(f, "{token}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The levels at which a CalVer version can be bumped, besides moving to a new date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum CalverLevel {
+    Major,
+    Minor,
+    #[default]
+    Micro,
+}
+
+impl CalverLevel {
+    fn name(self) -> &'static str {
+        match self {
+            CalverLevel::Major => "major",
+            CalverLevel::Minor => "minor",
+            CalverLevel::Micro => "micro",
+        }
+    }
+}
+
+/// A calendar version, e.g. `2024.10.3`, along with the format it follows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalverVersion {
+    pub format: CalverFormat,
+    values: Vec<u64>,
+}
+
+impl CalverVersion {
+    pub fn parse(input: &str, format: &CalverFormat) -> Result<Self, VersionError> {
+        let invalid = |reason: String| VersionError::InvalidCalver(input.to_owned(), reason);
+
+        let mut values = vec![];
+        let mut rest = input.trim();
+        for (i, token) in format.tokens.iter().enumerate() {
+            let segment = match format.separators.get(i) {
+                Some(separator) => {
+                    let (segment, tail) = rest
+                        .split_once(*separator)
+                        .ok_or_else(|| invalid(format!("expected the format {format}")))?;
+                    rest = tail;
+                    segment
+                }
+                None => std::mem::take(&mut rest),
+            };
+
+            let well_formed = !segment.is_empty()
+                && segment.chars().all(|c| c.is_ascii_digit())
+                && match token {
+                    Token::FullYear => segment.len() == 4,
+                    t if t.is_padded() => segment.len() == 2,
+                    _ => segment == "0" || !segment.starts_with('0'),
+                };
+            if !well_formed {
+                return Err(invalid(format!(
+                    "'{segment}' doesn't match the {token} segment"
+                )));
+            }
+            values.push(
+                segment
+                    .parse()
+                    .map_err(|_| invalid(format!("'{segment}' is too large")))?,
+            );
+        }
+
+        let version = Self {
+            format: format.clone(),
+            values,
+        };
+        version.validate_date().map_err(invalid)?;
+        Ok(version)
+    }
+
+    fn get(&self, name: &str) -> Option<u64> {
+        self.format.position(name).map(|i| self.values[i])
+    }
+
+    /// The value of the segment with the given name (`year`, `month`, `micro`...). Years are
+    /// always returned in full, even for short year formats.
+    pub fn segment(&self, name: &str) -> Option<u64> {
+        self.format.position(name).map(|i| self.full_value(i))
+    }
+
+    fn reset_below(&mut self, level: CalverLevel) {
+        let lower: &[CalverLevel] = match level {
+            CalverLevel::Major => &[CalverLevel::Minor, CalverLevel::Micro],
+            CalverLevel::Minor => &[CalverLevel::Micro],
+            CalverLevel::Micro => &[],
+        };
+        for level in lower {
+            if let Some(i) = self.format.position(level.name()) {
+                self.values[i] = 0;
+            }
+        }
+    }
+
+    fn full_value(&self, i: usize) -> u64 {
+        match self.format.tokens[i] {
+            Token::ShortYear | Token::PaddedYear => self.values[i] + 2000,
+            _ => self.values[i],
+        }
+    }
+
+    fn validate_date(&self) -> Result<(), String> {
+        let year = self.segment("year");
+        if let Some(month) = self.get("month")
+            && !(1..=12).contains(&month)
+        {
+            return Err(format!("{month} is not a valid month"));
+        }
+        if let Some(week) = self.get("week")
+            && !(1..=53).contains(&week)
+        {
+            return Err(format!("{week} is not a valid week"));
+        }
+        if let Some(day) = self.get("day") {
+            let valid = match (year, self.get("month")) {
+                (Some(year), Some(month)) => NaiveDate::from_ymd_opt(
+                    year.try_into().unwrap_or(i32::MAX),
+                    month as u32,
+                    day.try_into().unwrap_or(0),
+                )
+                .is_some(),
+                _ => (1..=31).contains(&day),
+            };
+            if !valid {
+                return Err(format!("{day} is not a valid day"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Bump the version to the given date. When the date segments change, the minor and micro
+    /// segments are reset to 0. Otherwise the segment given by `level` (micro by default) is
+    /// incremented, resetting the segments below it. The major segment only changes when it is
+    /// bumped explicitly.
+    pub fn bump(
+        &mut self,
+        level: Option<CalverLevel>,
+        date: &NaiveDate,
+    ) -> Result<(), VersionError> {
+        let bump_error = |reason: String| VersionError::CalverBump(self.to_string(), reason);
+
+        let mut bumped = self.clone();
+        for (token, value) in bumped.format.tokens.iter().zip(bumped.values.iter_mut()) {
+            if token.is_date() {
+                *value = token.for_date(date);
+            }
+        }
+        let date_changed = bumped.values != self.values;
+
+        let level = level.unwrap_or_default();
+        if level == CalverLevel::Major || !date_changed {
+            let i = bumped
+                .format
+                .position(level.name())
+                .ok_or_else(|| bump_error(format!("the format has no {} segment", level.name())))?;
+            bumped.values[i] += 1;
+            bumped.reset_below(level);
+        } else {
+            bumped.reset_below(CalverLevel::Major);
+        }
+
+        if bumped < *self {
+            return Err(bump_error(format!(
+                "{date} is before the date of the version"
+            )));
+        }
+        *self = bumped;
+        Ok(())
+    }
+}
+
+impl Ord for CalverVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |v: &Self| {
+            (0..v.values.len())
+                .map(|i| v.full_value(i))
+                .collect::<Vec<_>>()
+        };
+        key(self)
+            .cmp(&key(other))
+            .then_with(|| self.format.to_string().cmp(&other.format.to_string()))
+    }
+}
+
+impl PartialOrd for CalverVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for CalverVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (token, value)) in self.format.tokens.iter().zip(&self.values).enumerate() {
+            if i > 0 {
+                write!(f, "{}", self.format.separators[i - 1])?;
+            }
+            if token.is_padded() {
+                write!(f, "{value:02}")?;
+            } else {
+                write!(f, "{value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse a date in the `YYYY-MM-DD` format. Anything after the day (e.g. a time) is ignored.
+pub fn parse_date(input: &str) -> Result<NaiveDate, VersionError> {
+    NaiveDate::parse_and_remainder(input, "%Y-%m-%d")
+        .ok()
+        .map(|(date, _)| date)
+        .filter(|date| date.year() >= 2000)
+        .ok_or_else(|| VersionError::InvalidDate(input.to_owned()))
+}
+
+/// Today's date, in UTC.
+pub fn today() -> NaiveDate {
+    DateTime::<Utc>::from(SystemTime::now()).date_naive()
+}
@@ -0,0 +1,373 @@
+use chrono::NaiveDate;
+
+/// The templates used to render a changelog section.
+#[derive(Debug, Clone)]
+pub struct Template {
+    /// The heading of the section, with `{version}` and `{date}` placeholders.
+    pub heading: String,
+    /// A changelog entry, with `{breaking}`, `{scope}`, `{type}`, `{description}` and `{message}`
+    /// placeholders.
+    pub entry: String,
+    /// The title of the group of each commit type, in order.
+    pub groups: Vec<(String, String)>,
+    /// The title of the group of the other commits.
+    pub other: String,
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        let groups = [
+            ("feat", "Added"),
+            ("fix", "Fixed"),
+            ("perf", "Changed"),
+            ("refactor", "Changed"),
+        ];
+        Self {
+            heading: "## [{version}] - {date}".to_owned(),
+            entry: "- {breaking}{scope}{description}".to_owned(),
+            groups: groups
+                .iter()
+                .map(|(kind, title)| (kind.to_string(), title.to_string()))
+                .collect(),
+            other: "Other".to_owned(),
+        }
+    }
+}
+
+/// A commit to list in a changelog.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub kind: Option<String>,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+    pub message: String,
+}
+
+impl Template {
+    /// Render the section of a release, grouping the entries by their type.
+    pub fn render(&self, version: &semver::Version, date: &str, entries: &[Entry]) -> String {
+        let mut titles: Vec<&str> = vec![];
+        for (_, title) in &self.groups {
+            if !titles.contains(&title.as_str()) {
+                titles.push(title);
+            }
+        }
+        if !titles.contains(&self.other.as_str()) {
+            titles.push(&self.other);
+        }
+
+        let mut section = fill(
+            &self.heading,
+            &[("version", &version.to_string()), ("date", date)],
+        );
+        section.push('\n');
+        for title in titles {
+            let lines: Vec<String> = entries
+                .iter()
+                .filter(|entry| self.group(entry) == title)
+                .map(|entry| self.render_entry(entry))
+                .collect();
+            if !lines.is_empty() {
+                section.push_str(&format!("\n### {title}\n\n"));
+                for line in lines {
+                    section.push_str(&line);
+                    section.push('\n');
+                }
+            }
+        }
+        section
+    }
+
+    fn group(&self, entry: &Entry) -> &str {
+        entry
+            .kind
+            .as_deref()
+            .and_then(|kind| self.groups.iter().find(|(k, _)| k == kind))
+            .map_or(&self.other, |(_, title)| title)
+    }
+
+    fn render_entry(&self, entry: &Entry) -> String {
+        let breaking = if entry.breaking {
+            "[**breaking**] "
+        } else {
+            ""
+        };
+        let scope = entry
+            .scope
+            .as_ref()
+            .map_or(String::new(), |scope| format!("*({scope})* "));
+        fill(
+            &self.entry,
+            &[
+                ("breaking", breaking),
+                ("scope", &scope),
+                ("type", entry.kind.as_deref().unwrap_or("")),
+                ("description", &entry.description),
+                ("message", &entry.message),
+            ],
+        )
+    }
+}
+
+/// Replace the `{name}` placeholders of a template. The template is scanned once, so the values
+/// are inserted as is, even if they contain placeholders themselves. Unknown placeholders are
+/// kept.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest.find('}').and_then(|end| {
+            let name = &rest[1..end];
+            values
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                text.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Insert a section in a changelog, right under the `Unreleased` heading. Without such a heading,
+/// the section is inserted before the first release, or at the end.
+pub fn prepend(changelog: &str, section: &str) -> String {
+    let lines: Vec<&str> = changelog.lines().collect();
+    let is_unreleased = |line: &&str| {
+        line.strip_prefix("## ").is_some_and(|title| {
+            title
+                .trim_matches(|c: char| c == '[' || c == ']' || c.is_whitespace())
+                .eq_ignore_ascii_case("unreleased")
+        })
+    };
+
+    let (before, after) = if let Some(i) = lines.iter().position(is_unreleased) {
+        (&lines[..=i], &lines[i + 1..])
+    } else if let Some(i) = lines.iter().position(|line| line.starts_with("## ")) {
+        (&lines[..i], &lines[i..])
+    } else {
+        (&lines[..], &[][..])
+    };
+
+    let before = before.join("\n");
+    let after = after.join("\n");
+    let mut result = before.trim_end().to_owned();
+    if !result.is_empty() {
+        result.push_str("\n\n");
+    }
+    result.push_str(section.trim_end());
+    result.push('\n');
+    let after = after.trim_start_matches('\n');
+    if !after.is_empty() {
+        result.push('\n');
+        result.push_str(after);
+        result.push('\n');
+    }
+    result
+}
+
+/// A release section of a changelog.
+#[derive(Debug, Clone)]
+pub struct Release {
+    pub version: semver::Version,
+    pub date: Option<NaiveDate>,
+    /// The contents of the section, without its heading.
+    pub body: String,
+    /// The entries of each subsection (e.g. Added or Fixed), in order.
+    pub subsections: Vec<(String, Vec<String>)>,
+}
+
+/// Parse the release sections of a changelog in the Keep a Changelog format, including the
+/// variants generated by git-cliff and release-plz, e.g. `## [1.2.3](<compare link>) - 2024-05-01`.
+/// Sections whose heading isn't a version (e.g. Unreleased) are skipped.
+pub fn parse(changelog: &str) -> Vec<Release> {
+    let mut releases = vec![];
+    let mut current: Option<(Release, Vec<&str>)> = None;
+    for line in changelog.lines() {
+        if let Some(title) = line.strip_prefix("## ") {
+            releases.extend(current.take().map(finish));
+            current = parse_heading(title).map(|release| (release, vec![]));
+        } else if let Some((_, lines)) = &mut current {
+            lines.push(line);
+        }
+    }
+    releases.extend(current.map(finish));
+    releases
+}
+
+/// Parse a release heading, e.g. `[1.2.3] - 2024-05-01`.
+fn parse_heading(title: &str) -> Option<Release> {
+    let title = title.trim();
+    let (version, rest) = match title.strip_prefix('[') {
+        Some(title) => title.split_once(']')?,
+        None => title.split_once(char::is_whitespace).unwrap_or((title, "")),
+    };
+    let version = version.trim();
+    let version = semver::Version::parse(version.strip_prefix('v').unwrap_or(version)).ok()?;
+    let date = (0..rest.len())
+        .filter_map(|i| rest.get(i..i + 10))
+        .filter(|s| {
+            s.char_indices().all(|(i, c)| match i {
+                4 | 7 => c == '-',
+                _ => c.is_ascii_digit(),
+            })
+        })
+        .find_map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
+    Some(Release {
+        version,
+        date,
+        body: String::new(),
+        subsections: vec![],
+    })
+}
+
+fn finish((mut release, lines): (Release, Vec<&str>)) -> Release {
+    release.body = lines.join("\n").trim().to_owned();
+    for line in lines {
+        if let Some(title) = line.strip_prefix("### ") {
+            release.subsections.push((title.trim().to_owned(), vec![]));
+        } else if let Some((_, entries)) = release.subsections.last_mut() {
+            if let Some(entry) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+                entries.push(entry.trim().to_owned());
+            } else if let Some(entry) = entries.last_mut()
+                && line.starts_with(char::is_whitespace)
+                && !line.trim().is_empty()
+            {
+                // Continuation of a multi-line entry
+                entry.push('\n');
+                entry.push_str(line.trim());
+            }
+        }
+    }
+    release
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+    }
+
+    #[test]
+    fn parse_generated_headings() {
+        let releases = parse(
+            "# Changelog
+
+## [Unreleased]
+
+## [1.2.3](<https://example.com/compare/v1.2.2..v1.2.3>) - 2024-05-01
+
+### Fixed
+
+- a crash
+
+## [1.2.2](https://example.com/compare/v1.2.1...v1.2.2) - 2024-04-02
+
+## v1.2.1 - 2024-03-03
+
+## [1.2.0]
+",
+        );
+        let headings: Vec<_> = releases
+            .iter()
+            .map(|r| (r.version.to_string(), r.date))
+            .collect();
+        assert_eq!(
+            headings,
+            vec![
+                ("1.2.3".to_owned(), date("2024-05-01")),
+                ("1.2.2".to_owned(), date("2024-04-02")),
+                ("1.2.1".to_owned(), date("2024-03-03")),
+                ("1.2.0".to_owned(), None),
+            ]
+        );
+        assert_eq!(releases[0].body, "### Fixed\n\n- a crash");
+        assert_eq!(
+            releases[0].subsections,
+            vec![("Fixed".to_owned(), vec!["a crash".to_owned()])]
+        );
+    }
+
+    #[test]
+    fn parse_multi_line_entries() {
+        let releases = parse(
+            "## [1.0.0] - 2024-01-02
+
+### Changed
+
+- the parser now accepts
+  partial versions,
+  e.g. `1.2`
+
+* ranges
+- wildcards
+",
+        );
+        assert_eq!(
+            releases[0].subsections,
+            vec![(
+                "Changed".to_owned(),
+                vec![
+                    "the parser now accepts\npartial versions,\ne.g. `1.2`".to_owned(),
+                    "ranges".to_owned(),
+                    "wildcards".to_owned(),
+                ]
+            )]
+        );
+    }
+
+    const SECTION: &str = "## [1.1.0] - 2024-05-01\n\n### Added\n\n- ranges\n";
+
+    #[test]
+    fn fill_placeholders_once() {
+        assert_eq!(
+            fill(
+                "- {scope}: {description} {unknown}",
+                &[("description", "support {scope}"), ("scope", "parser")]
+            ),
+            "- parser: support {scope} {unknown}"
+        );
+    }
+
+    #[test]
+    fn prepend_under_unreleased() {
+        assert_eq!(
+            prepend(
+                "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2024-01-02\n\n- first release\n",
+                SECTION
+            ),
+            "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2024-05-01\n\n### Added\n\n- ranges\n\n## [1.0.0] - 2024-01-02\n\n- first release\n"
+        );
+    }
+
+    #[test]
+    fn prepend_without_unreleased() {
+        assert_eq!(
+            prepend(
+                "# Changelog\n\nAll notable changes.\n\n## [1.0.0] - 2024-01-02\n\n- first release\n",
+                SECTION
+            ),
+            "# Changelog\n\nAll notable changes.\n\n## [1.1.0] - 2024-05-01\n\n### Added\n\n- ranges\n\n## [1.0.0] - 2024-01-02\n\n- first release\n"
+        );
+        // Without any release, the section is added at the end
+        assert_eq!(
+            prepend("# Changelog\n\nAll notable changes.\n", SECTION),
+            "# Changelog\n\nAll notable changes.\n\n## [1.1.0] - 2024-05-01\n\n### Added\n\n- ranges\n"
+        );
+        assert_eq!(prepend("", SECTION), SECTION);
+    }
+}
@@ -1,25 +1,77 @@
-use nu_plugin::PluginCommand;
-use nu_protocol::Type;
+use chrono::NaiveDate;
+use nu_plugin::{EvaluatedCall, PluginCommand};
+use nu_protocol::{LabeledError, ShellError, Signature, Spanned, SyntaxShape, Type, Value};
 
-use crate::SemverPlugin;
+use crate::{
+    SemverPlugin,
+    calver::{CalverFormat, DEFAULT_FORMAT, parse_date, today},
+    scheme::Scheme,
+    version::{Dialect, Level},
+};
 
 mod bump;
+mod calver;
+mod changelog;
+mod coerce;
+mod commits;
+mod compare;
+mod diff;
 mod from_record;
+mod git;
+mod go;
+mod into_calver;
+mod into_pep440;
 mod into_semver;
+mod into_semver_req;
+mod manifest;
 mod match_req;
+mod maven;
+mod max_min;
+mod pep440;
+mod req_algebra;
+mod req_explain;
 mod root;
 mod sort;
 mod to_record;
+mod workspace;
 
 pub fn commands() -> Vec<Box<dyn PluginCommand<Plugin = SemverPlugin>>> {
     vec![
         Box::new(root::SemverCommand),
         Box::new(into_semver::IntoSemver),
+        Box::new(into_semver_req::IntoSemverReq),
         Box::new(to_record::SemverToRecord),
         Box::new(from_record::SemverFromRecord),
         Box::new(bump::SemverBump),
         Box::new(sort::SemverSort),
         Box::new(match_req::SemverMatchReq),
+        Box::new(coerce::SemverCoerce),
+        Box::new(max_min::SemverMax),
+        Box::new(max_min::SemverMin),
+        Box::new(max_min::SemverMaxSatisfying),
+        Box::new(max_min::SemverMinSatisfying),
+        Box::new(diff::SemverDiff),
+        Box::new(req_algebra::SemverReqIntersect),
+        Box::new(req_algebra::SemverReqUnion),
+        Box::new(req_algebra::SemverReqSimplify),
+        Box::new(req_algebra::SemverReqIsEmpty),
+        Box::new(req_explain::SemverReqExplain),
+        Box::new(into_pep440::IntoPep440),
+        Box::new(pep440::SemverPep440Match),
+        Box::new(pep440::SemverPep440ToSemver),
+        Box::new(compare::SemverCompare),
+        Box::new(into_calver::IntoCalver),
+        Box::new(calver::SemverCalverBump),
+        Box::new(go::SemverGoParse),
+        Box::new(maven::SemverMavenMatch),
+        Box::new(manifest::SemverManifestGet),
+        Box::new(manifest::SemverManifestSet),
+        Box::new(workspace::SemverWorkspaceBump),
+        Box::new(git::SemverGitNext),
+        Box::new(git::SemverGitTags),
+        Box::new(commits::SemverCommitsClassify),
+        Box::new(changelog::SemverChangelogRender),
+        Box::new(changelog::SemverChangelogParse),
     ]
 }
 
@@ -39,3 +91,133 @@ fn record_type() -> Type {
 fn custom_type() -> Type {
     Type::Custom("semver".into())
 }
+
+fn req_custom_type() -> Type {
+    Type::Custom("semver-req".into())
+}
+
+fn pep440_custom_type() -> Type {
+    Type::Custom("pep440".into())
+}
+
+fn calver_custom_type() -> Type {
+    Type::Custom("calver".into())
+}
+
+/// Get the value of the given column in a table row.
+#[allow(clippy::result_large_err)]
+fn get_column<'a>(row: &'a Value, column: &Spanned<String>) -> Result<&'a Value, ShellError> {
+    row.as_record()?
+        .get(&column.item)
+        .ok_or_else(|| ShellError::CantFindColumn {
+            col_name: column.item.clone(),
+            span: Some(column.span),
+            src_span: row.span(),
+        })
+}
+
+/// Add the `--dialect` flag, selecting how version requirements given as strings are parsed.
+fn dialect_flag(signature: Signature) -> Signature {
+    signature.named(
+        "dialect",
+        SyntaxShape::String,
+        "The syntax of version requirements given as strings: 'cargo' (the default) or 'npm'",
+        Some('d'),
+    )
+}
+
+/// Get the value of the `--dialect` flag.
+#[allow(clippy::result_large_err)]
+fn get_dialect(call: &EvaluatedCall) -> Result<Dialect, ShellError> {
+    match call.get_flag::<Spanned<String>>("dialect")? {
+        Some(dialect) => dialect
+            .item
+            .parse()
+            .map_err(|_| ShellError::IncorrectValue {
+                msg: "dialect should be either 'cargo' or 'npm'".into(),
+                val_span: dialect.span,
+                call_span: call.head,
+            }),
+        None => Ok(Dialect::default()),
+    }
+}
+
+/// Add the `--scheme` flag, selecting how versions are parsed and ordered.
+fn scheme_flag(signature: Signature) -> Signature {
+    signature.named(
+        "scheme",
+        SyntaxShape::String,
+        "The versioning scheme: 'semver' (the default), 'pep440', 'deb', 'rpm', 'go' or 'maven'",
+        Some('s'),
+    )
+}
+
+/// Get the value of the `--scheme` flag.
+#[allow(clippy::result_large_err)]
+fn get_scheme(call: &EvaluatedCall) -> Result<Scheme, ShellError> {
+    match call.get_flag::<Spanned<String>>("scheme")? {
+        Some(scheme) => scheme.item.parse().map_err(|_| ShellError::IncorrectValue {
+            msg: "scheme should be one of 'semver', 'pep440', 'deb', 'rpm', 'go' or 'maven'".into(),
+            val_span: scheme.span,
+            call_span: call.head,
+        }),
+        None => Ok(Scheme::default()),
+    }
+}
+
+/// Add the `--format` flag, giving the format of CalVer versions.
+fn calver_format_flag(signature: Signature) -> Signature {
+    signature.named(
+        "format",
+        SyntaxShape::String,
+        "The format of the versions, e.g. YYYY.0M.MICRO (the default)",
+        Some('f'),
+    )
+}
+
+/// Get the value of the `--format` flag.
+fn get_calver_format(call: &EvaluatedCall) -> Result<CalverFormat, LabeledError> {
+    match call.get_flag::<Spanned<String>>("format")? {
+        Some(format) => {
+            CalverFormat::parse(&format.item).map_err(|e| e.into_labeled_error(format.span))
+        }
+        None => Ok(CalverFormat::parse(DEFAULT_FORMAT).expect("the default format is valid")),
+    }
+}
+
+/// Get the value of the `--date` flag, given as a date or a YYYY-MM-DD string. Defaults to
+/// today's date.
+fn get_date(call: &EvaluatedCall) -> Result<NaiveDate, LabeledError> {
+    match call.get_flag::<Value>("date")? {
+        Some(Value::Date {
+            val, internal_span, ..
+        }) => parse_date(&val.format("%Y-%m-%d").to_string())
+            .map_err(|e| e.into_labeled_error(internal_span)),
+        Some(value) => {
+            parse_date(&value.coerce_str()?).map_err(|e| e.into_labeled_error(value.span()))
+        }
+        None => Ok(today()),
+    }
+}
+
+/// Get the bump level given as the positional argument at `index`, along with the value of the
+/// `--label` flag, which can only be used with the levels taking a label.
+fn get_level(call: &EvaluatedCall, index: usize) -> Result<(Level, Option<String>), LabeledError> {
+    let level: Spanned<String> = call.req(index)?;
+    let level = level.item.parse::<Level>().map_err(|e| {
+        LabeledError::new(
+            "Valid levels are: major, minor, patch, alpha, beta, rc, pre, premajor, preminor, prepatch, prerelease, release",
+        )
+        .with_label(e.to_string(), level.span)
+    })?;
+    let label: Option<Spanned<String>> = call.get_flag("label")?;
+    if let Some(label) = &label
+        && !level.takes_label()
+    {
+        return Err(LabeledError::new("Invalid flag").with_label(
+            "--label can only be used with the pre, premajor, preminor, prepatch and prerelease levels",
+            label.span,
+        ));
+    }
+    Ok((level, label.map(|l| l.item)))
+}
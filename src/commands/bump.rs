@@ -1,9 +1,11 @@
-use nu_plugin::{EvaluatedCall, SimplePluginCommand};
-use nu_protocol::{Example, LabeledError, Signature, Spanned, SyntaxShape, Type, Value};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Example, LabeledError, Signature, SyntaxShape, Type, Value};
 
-use crate::{SemverPlugin, custom_value::SemverCustomValue, version::Level};
+use crate::{
+    SemverPlugin, config::SemverConfig, custom_value::SemverCustomValue, version::PrereleaseLabels,
+};
 
-use super::custom_type;
+use super::{custom_type, get_level};
 
 pub struct SemverBump;
 
@@ -27,10 +29,22 @@ impl SimplePluginCommand for SemverBump {
                     "Additionally set the build metadata",
                     Some('b')
                 )
+                .named(
+                    "label",
+                    SyntaxShape::String,
+                    "The pre-release label to use with the `pre`, `premajor`, `preminor`, `prepatch` and `prerelease` levels. Defaults to the current label, or the first label of the chain",
+                    Some('l')
+                )
+                .named(
+                    "labels",
+                    SyntaxShape::List(Box::new(SyntaxShape::String)),
+                    "The ordered chain of pre-release labels. Defaults to the `prerelease_labels` setting of the plugin configuration",
+                    None
+                )
                 .required(
                     "level",
                     SyntaxShape::String,
-                    "The version level to bump. Valid values are: major, minor, patch, alpha, beta, rc, or release."
+                    "The version level to bump. Valid values are: major, minor, patch, alpha, beta, rc, pre, premajor, preminor, prepatch, prerelease, or release."
                 )
                 .input_output_types(vec![
                     (Type::String, custom_type()),
@@ -74,6 +88,36 @@ impl SimplePluginCommand for SemverBump {
                 description: "Bump version to next alpha pre-release",
                 result: Some(SemverCustomValue::test_value("1.2.4-alpha.1")),
             },
+            Example {
+                example: r#""1.2.3" | semver bump rc"#,
+                description: "Start a release candidate of the version",
+                result: Some(SemverCustomValue::test_value("1.2.3-rc.1")),
+            },
+            Example {
+                example: r#""1.2.3-dev.2" | semver bump pre --labels [dev preview] --label preview"#,
+                description: "Bump to the next label of a custom pre-release chain",
+                result: Some(SemverCustomValue::test_value("1.2.3-preview.1")),
+            },
+            Example {
+                example: r#""1.2.3" | semver bump pre --labels [dev preview]"#,
+                description: "Start a pre-release with the first label of a custom pre-release chain",
+                result: Some(SemverCustomValue::test_value("1.2.4-dev.1")),
+            },
+            Example {
+                example: r#""1.2.3" | semver bump premajor"#,
+                description: "Start a pre-release cycle for the next major version",
+                result: Some(SemverCustomValue::test_value("2.0.0-alpha.1")),
+            },
+            Example {
+                example: r#""1.2.3" | semver bump preminor --label beta"#,
+                description: "Start a pre-release cycle for the next minor version with a given label",
+                result: Some(SemverCustomValue::test_value("1.3.0-beta.1")),
+            },
+            Example {
+                example: r#""1.2.3-nightly.4" | semver bump prerelease"#,
+                description: "Increment a pre-release with an arbitrary identifier",
+                result: Some(SemverCustomValue::test_value("1.2.3-nightly.5")),
+            },
             Example {
                 example: r#""1.2.3-rc.1" | semver bump release"#,
                 description: "Release the current pre-release version",
@@ -85,7 +129,7 @@ impl SimplePluginCommand for SemverBump {
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &nu_plugin::EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         input: &Value,
     ) -> Result<Value, LabeledError> {
@@ -93,16 +137,19 @@ impl SimplePluginCommand for SemverBump {
         let span = call.head;
 
         let res = {
-            let mut version: SemverCustomValue = input.try_into()?;
-            let level: Spanned<String> = call.req(0)?;
-            let level = level.item.parse::<Level>().map_err(|e| {
-                LabeledError::new("Valid levels are: major, minor, patch, alpha, beta, rc")
-                    .with_label(e.to_string(), level.span)
-            })?;
+            let config = SemverConfig::from_engine(engine)?;
+            let mut version = SemverCustomValue::try_from_value(input, config.lenient)?;
+            let (level, label) = get_level(call, 0)?;
+            let labels = match call.get_flag::<Vec<String>>("labels")? {
+                Some(labels) => {
+                    PrereleaseLabels::new(labels).map_err(|e| e.into_labeled_error(span))?
+                }
+                None => config.prerelease_labels,
+            };
             let meta: Option<String> = call.get_flag("build-metadata")?;
 
             version
-                .bump(level, meta)
+                .bump(level, label.as_deref(), &labels, meta)
                 .map_err(|e| e.into_labeled_error(span))?;
 
             Ok(version)
@@ -0,0 +1,97 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Example, LabeledError, Signature, Spanned, SyntaxShape, Type, Value};
+
+use crate::{
+    SemverPlugin,
+    calver::{CalverLevel, DEFAULT_FORMAT},
+    custom_value::CalverCustomValue,
+};
+
+use super::{calver_custom_type, calver_format_flag, get_calver_format, get_date};
+
+pub struct SemverCalverBump;
+
+impl SimplePluginCommand for SemverCalverBump {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver calver bump"
+    }
+
+    fn description(&self) -> &str {
+        "Bump a calendar version (CalVer) to the current date"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The date segments are set to today's date (in UTC), or to the date given with `--date`. When the date segments change, the MINOR and MICRO segments are reset to 0. Otherwise the segment given by `level` (MICRO by default) is incremented. MAJOR is only incremented when it is the level being bumped."
+    }
+
+    fn signature(&self) -> Signature {
+        calver_format_flag(Signature::build(self.name()))
+            .optional(
+                "level",
+                SyntaxShape::String,
+                "The segment to increment if the date didn't change: major, minor or micro. Defaults to micro.",
+            )
+            .named(
+                "date",
+                SyntaxShape::Any,
+                "The date to bump to, as a date or a YYYY-MM-DD string. Defaults to today",
+                Some('d'),
+            )
+            .input_output_types(vec![
+                (Type::String, calver_custom_type()),
+                (calver_custom_type(), calver_custom_type()),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""2024.10.3" | semver calver bump --date 2024-10-17"#,
+                description: "Increment MICRO when the date didn't change",
+                result: Some(CalverCustomValue::test_value("2024.10.4", DEFAULT_FORMAT)),
+            },
+            Example {
+                example: r#""2024.10.3" | semver calver bump --date 2024-11-02"#,
+                description: "Move to a new month",
+                result: Some(CalverCustomValue::test_value("2024.11.0", DEFAULT_FORMAT)),
+            },
+            Example {
+                example: r#""24.04.1.5" | semver calver bump --format YY.0M.MINOR.MICRO minor --date "2024-04-30""#,
+                description: "Increment MINOR, resetting MICRO",
+                result: Some(CalverCustomValue::test_value(
+                    "24.04.2.0",
+                    "YY.0M.MINOR.MICRO",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let format = get_calver_format(call)?;
+        let mut version = CalverCustomValue::try_from_value(input, &format)?;
+
+        let level = match call.opt::<Spanned<String>>(0)? {
+            Some(level) => Some(level.item.parse::<CalverLevel>().map_err(|_| {
+                LabeledError::new("Valid levels are: major, minor, micro")
+                    .with_label("Invalid level", level.span)
+            })?),
+            None => None,
+        };
+        let date = get_date(call)?;
+
+        version
+            .0
+            .bump(level, &date)
+            .map_err(|e| e.into_labeled_error(span))?;
+        Ok(version.into_value(span))
+    }
+}
@@ -0,0 +1,351 @@
+use std::{fs, path::PathBuf};
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Example, LabeledError, Record, ShellError, Signature, Spanned, SyntaxShape, Type, Value, record,
+};
+
+use crate::{
+    SemverPlugin,
+    changelog::{self, Entry, Template},
+    conventional::ConventionalCommit,
+    custom_value::SemverCustomValue,
+};
+
+use super::{custom_type, get_date};
+
+pub struct SemverChangelogRender;
+pub struct SemverChangelogParse;
+
+impl SimplePluginCommand for SemverChangelogRender {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver changelog render"
+    }
+
+    fn description(&self) -> &str {
+        "Render the changelog section of a release from its commits, in the Keep a Changelog format"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The input is the output of `semver commits classify`, a table of classified commits, or a list of commit messages. Entries are grouped by commit type: `feat` under "Added", `fix` under "Fixed", `perf` and `refactor` under "Changed", and the other commits under "Other".
+
+The templates can be changed with `--template`, a record with any of these keys:
+- `heading`: the heading of the section, with `{version}` and `{date}` placeholders
+- `entry`: each entry, with `{breaking}`, `{scope}`, `{type}`, `{description}` and `{message}` placeholders
+- `groups`: a record of group titles by commit type, replacing the default groups
+- `other`: the title of the group of the other commits
+
+With `--prepend`, the section is also inserted in the given changelog file, under its `Unreleased` heading."#
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "version",
+                SyntaxShape::Any,
+                "The version of the release, either as a string or a semver value",
+            )
+            .named(
+                "date",
+                SyntaxShape::Any,
+                "The date of the release, as a date or a YYYY-MM-DD string. Defaults to today",
+                Some('d'),
+            )
+            .named(
+                "template",
+                SyntaxShape::Record(vec![]),
+                "Templates overriding the default ones",
+                Some('t'),
+            )
+            .named(
+                "prepend",
+                SyntaxShape::Filepath,
+                "A changelog file to insert the section into",
+                Some('p'),
+            )
+            .input_output_types(vec![
+                (Type::record(), Type::String),
+                (Type::table(), Type::String),
+                (Type::List(Box::new(Type::String)), Type::String),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"["feat(parser): support ranges", "fix: handle empty input"] | semver commits classify | semver changelog render "1.3.0" --date 2024-05-01"#,
+                description: "Render the changelog section of a release",
+                result: Some(Value::test_string(
+                    "## [1.3.0] - 2024-05-01\n\n### Added\n\n- *(parser)* support ranges\n\n### Fixed\n\n- handle empty input\n",
+                )),
+            },
+            Example {
+                example: r###"["feat!: drop the old API", "chore: update deps"] | semver changelog render "2.0.0" --date 2024-05-01 --template {heading: "## {version} ({date})", groups: {feat: Features}}"###,
+                description: "Render a changelog section with custom templates",
+                result: Some(Value::test_string(
+                    "## 2.0.0 (2024-05-01)\n\n### Features\n\n- [**breaking**] drop the old API\n\n### Other\n\n- update deps\n",
+                )),
+            },
+            Example {
+                example: r#"semver commits classify | semver changelog render "1.3.0" --prepend CHANGELOG.md"#,
+                description: "Add the section of a release to a changelog",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let SemverCustomValue(version) = SemverCustomValue::try_from_value(&call.req(0)?, false)?;
+        let date = get_date(call)?;
+        let template = match call.get_flag::<Value>("template")? {
+            Some(value) => parse_template(&value)?,
+            None => Template::default(),
+        };
+
+        let commits = match input {
+            Value::Record { val, .. } => {
+                val.get("commits")
+                    .ok_or_else(|| ShellError::CantFindColumn {
+                        col_name: "commits".into(),
+                        span: None,
+                        src_span: input.span(),
+                    })?
+            }
+            _ => input,
+        };
+        let entries = commits
+            .as_list()?
+            .iter()
+            .map(entry)
+            .collect::<Result<Vec<_>, _>>()?;
+        let section = template.render(&version, &date.to_string(), &entries);
+
+        if let Some(path) = call.get_flag::<Spanned<String>>("prepend")? {
+            let full_path = PathBuf::from(engine.get_current_dir()?).join(&path.item);
+            let io_error = |e: std::io::Error| {
+                LabeledError::new("Can't update the changelog").with_label(e.to_string(), path.span)
+            };
+            let changelog = fs::read_to_string(&full_path).map_err(io_error)?;
+            fs::write(&full_path, changelog::prepend(&changelog, &section)).map_err(io_error)?;
+        }
+        Ok(Value::string(section, span))
+    }
+}
+
+impl SimplePluginCommand for SemverChangelogParse {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver changelog parse"
+    }
+
+    fn description(&self) -> &str {
+        "Parse a changelog into a table with one row per release"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Supports the Keep a Changelog format, including the variants generated by git-cliff and release-plz, e.g. `## [1.2.3](<compare link>) - 2024-05-01`. Sections whose heading isn't a version (e.g. Unreleased) are skipped. The changelog is read from the given file, or from the input."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "The changelog file (defaults to reading the input)",
+            )
+            .input_output_types(vec![
+                (Type::Nothing, release_type()),
+                (Type::String, release_type()),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r###"("## [Unreleased]\n\n## [1.1.0] - 2024-05-01\n\n### Added\n\n- ranges\n\n## [1.0.0] - 2024-01-02\n" | semver changelog parse).version"###,
+                description: "Get the versions of a changelog",
+                result: Some(Value::test_list(vec![
+                    SemverCustomValue::test_value("1.1.0"),
+                    SemverCustomValue::test_value("1.0.0"),
+                ])),
+            },
+            Example {
+                example: r###"("## [0.2.0](https://example.com/compare/v0.1.0...v0.2.0) - 2024-05-01\n\n### Added\n\n- ranges\n- *(parser)* wildcards\n\n### Fixed\n\n- a crash\n" | semver changelog parse).0.subsections"###,
+                description: "Get the entries of each subsection of a release",
+                result: Some(Value::test_record(record!(
+                    "Added" => Value::test_list(vec![
+                        Value::test_string("ranges"),
+                        Value::test_string("*(parser)* wildcards"),
+                    ]),
+                    "Fixed" => Value::test_list(vec![Value::test_string("a crash")]),
+                ))),
+            },
+            Example {
+                example: "semver changelog parse CHANGELOG.md",
+                description: "Parse the changelog of the current project",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let text = match call.opt::<Spanned<String>>(0)? {
+            Some(path) => {
+                let full_path = PathBuf::from(engine.get_current_dir()?).join(&path.item);
+                fs::read_to_string(&full_path).map_err(|e| {
+                    LabeledError::new("Can't read the changelog")
+                        .with_label(e.to_string(), path.span)
+                })?
+            }
+            None => input.as_str()?.to_owned(),
+        };
+
+        let rows = changelog::parse(&text)
+            .into_iter()
+            .map(|release| {
+                let subsections = release
+                    .subsections
+                    .into_iter()
+                    .map(|(title, entries)| {
+                        let entries = entries
+                            .into_iter()
+                            .map(|e| Value::string(e, span))
+                            .collect();
+                        (title, Value::list(entries, span))
+                    })
+                    .collect::<Record>();
+                let date = release
+                    .date
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .map_or(Value::nothing(span), |date| {
+                        Value::date(date.and_utc().fixed_offset(), span)
+                    });
+                Value::record(
+                    record!(
+                        "version" => SemverCustomValue(release.version).into_value(span),
+                        "date" => date,
+                        "body" => Value::string(release.body, span),
+                        "subsections" => Value::record(subsections, span),
+                    ),
+                    span,
+                )
+            })
+            .collect();
+        Ok(Value::list(rows, span))
+    }
+}
+
+fn release_type() -> Type {
+    Type::Table(
+        vec![
+            ("version".to_string(), custom_type()),
+            ("date".to_string(), Type::Date),
+            ("body".to_string(), Type::String),
+            ("subsections".to_string(), Type::record()),
+        ]
+        .into(),
+    )
+}
+
+/// Convert a commit (a classified commit record, or a message) into a changelog entry.
+#[allow(clippy::result_large_err)]
+fn entry(value: &Value) -> Result<Entry, ShellError> {
+    let Value::Record { val, .. } = value else {
+        return Ok(parse_message(value.as_str()?));
+    };
+    let string = |column: &str| -> Result<Option<String>, ShellError> {
+        match val.get(column) {
+            None | Some(Value::Nothing { .. }) => Ok(None),
+            Some(value) => Ok(Some(value.as_str()?.to_owned())),
+        }
+    };
+    let message = string("message")?;
+    if !val.contains("type") {
+        // Not classified yet
+        let message = message.ok_or_else(|| ShellError::CantFindColumn {
+            col_name: "message".into(),
+            span: None,
+            src_span: value.span(),
+        })?;
+        return Ok(parse_message(&message));
+    }
+    let description = string("description")?;
+    Ok(Entry {
+        kind: string("type")?,
+        scope: string("scope")?,
+        breaking: val
+            .get("breaking")
+            .map(Value::as_bool)
+            .transpose()?
+            .unwrap_or(false),
+        description: description
+            .clone()
+            .or_else(|| message.clone())
+            .unwrap_or_default(),
+        message: message.or(description).unwrap_or_default(),
+    })
+}
+
+fn parse_message(message: &str) -> Entry {
+    let first_line = message.lines().next().unwrap_or_default().to_owned();
+    match ConventionalCommit::parse(message) {
+        Some(commit) => Entry {
+            kind: Some(commit.kind),
+            scope: commit.scope,
+            breaking: commit.breaking,
+            description: commit.description,
+            message: message.to_owned(),
+        },
+        None => Entry {
+            kind: None,
+            scope: None,
+            breaking: false,
+            description: first_line,
+            message: message.to_owned(),
+        },
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_template(value: &Value) -> Result<Template, LabeledError> {
+    let mut template = Template::default();
+    let record: &Record = value.as_record()?;
+    for (key, value) in record.iter() {
+        match key.as_str() {
+            "heading" => template.heading = value.as_str()?.to_owned(),
+            "entry" => template.entry = value.as_str()?.to_owned(),
+            "other" => template.other = value.as_str()?.to_owned(),
+            "groups" => {
+                template.groups = value
+                    .as_record()?
+                    .iter()
+                    .map(|(kind, title)| Ok((kind.clone(), title.as_str()?.to_owned())))
+                    .collect::<Result<_, ShellError>>()?;
+            }
+            _ => {
+                return Err(LabeledError::new("Invalid template").with_label(
+                    format!("unknown key {key}, expected heading, entry, groups or other"),
+                    value.span(),
+                ));
+            }
+        }
+    }
+    Ok(template)
+}
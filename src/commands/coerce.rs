@@ -0,0 +1,128 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Example, LabeledError, PipelineData, Record, Signature, Span, Type, Value, record,
+};
+
+use crate::{
+    SemverPlugin,
+    custom_value::SemverCustomValue,
+    version::{CoercedVersion, coerce, not_coercible_error},
+};
+
+use super::custom_type;
+
+pub struct SemverCoerce;
+
+impl PluginCommand for SemverCoerce {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver coerce"
+    }
+
+    fn description(&self) -> &str {
+        "Extract the best-effort SemVer version from a loosely formatted string"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The first run of up to three dot-separated numbers is used as the version, and missing components are set to 0. The returned record reports the text that was dropped before and after the version, as well as the components that were padded."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).input_output_types(vec![
+            (Type::String, coerced_type()),
+            (
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(coerced_type())),
+            ),
+        ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""v1.2" | semver coerce"#,
+                description: "Coerce a version with a prefix and a missing patch number",
+                result: Some(Value::test_record(record!(
+                    "version" => SemverCustomValue::test_value("1.2.0"),
+                    "prefix" => Value::test_string("v"),
+                    "suffix" => Value::test_string(""),
+                    "padded" => Value::test_list(vec![Value::test_string("patch")]),
+                ))),
+            },
+            Example {
+                example: r#""release-1.2.3.4" | semver coerce"#,
+                description: "Coerce a version with too many components",
+                result: Some(Value::test_record(record!(
+                    "version" => SemverCustomValue::test_value("1.2.3"),
+                    "prefix" => Value::test_string("release-"),
+                    "suffix" => Value::test_string(".4"),
+                    "padded" => Value::test_list(vec![]),
+                ))),
+            },
+            Example {
+                example: r#"("1-beta.2" | semver coerce).version"#,
+                description: "Valid pre-release identifiers are kept",
+                result: Some(SemverCustomValue::test_value("1.0.0-beta.2")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let head = call.head;
+
+        Ok(input.map(
+            move |v| {
+                let span = v.span();
+                match v.as_str() {
+                    Ok(s) => match coerce(s) {
+                        Some(coerced) => coerced_into_value(coerced, span),
+                        None => Value::error(not_coercible_error(span), head),
+                    },
+                    Err(e) => Value::error(e, head),
+                }
+            },
+            engine.signals(),
+        )?)
+    }
+}
+
+fn coerced_into_value(coerced: CoercedVersion, span: Span) -> Value {
+    let mut record = Record::new();
+    record.push(
+        "version",
+        SemverCustomValue(coerced.version).into_value(span),
+    );
+    record.push("prefix", Value::string(coerced.prefix, span));
+    record.push("suffix", Value::string(coerced.suffix, span));
+    record.push(
+        "padded",
+        Value::list(
+            coerced
+                .padded
+                .into_iter()
+                .map(|c| Value::string(c, span))
+                .collect(),
+            span,
+        ),
+    );
+    Value::record(record, span)
+}
+
+fn coerced_type() -> Type {
+    Type::Record(
+        vec![
+            ("version".to_string(), custom_type()),
+            ("prefix".to_string(), Type::String),
+            ("suffix".to_string(), Type::String),
+            ("padded".to_string(), Type::List(Box::new(Type::String))),
+        ]
+        .into(),
+    )
+}
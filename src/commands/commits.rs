@@ -0,0 +1,154 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Example, LabeledError, Record, Signature, Span, Spanned, SyntaxShape, Type, Value, record,
+};
+
+use crate::{
+    SemverPlugin,
+    conventional::{self, ConventionalCommit},
+    custom_value::SemverCustomValue,
+};
+
+use super::get_column;
+
+pub struct SemverCommitsClassify;
+
+impl SimplePluginCommand for SemverCommitsClassify {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver commits classify"
+    }
+
+    fn description(&self) -> &str {
+        "Classify commit messages following Conventional Commits, and the version level they require"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Returns a record with the classified `commits`, and the summary `level`: `major` if any commit is breaking (`!` or a `BREAKING CHANGE` footer), `minor` if any is a `feat`, `patch` if any is a `fix`, or nothing. The level can be given to `semver bump`. Messages that aren't conventional commits have an empty type. For tables, the columns of each row are kept."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .named(
+                "column",
+                SyntaxShape::String,
+                "The column holding the commit messages, for tables (defaults to `message`)",
+                Some('c'),
+            )
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::String)), classify_type()),
+                (Type::table(), classify_type()),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"(["feat(parser): support ranges", "fix: typo", "chore: update deps"] | semver commits classify).level"#,
+                description: "Get the level required by a list of commits",
+                result: Some(Value::test_string("minor")),
+            },
+            Example {
+                example: r#"(["feat(api)!: remove the old API"] | semver commits classify).commits.0"#,
+                description: "Classify a breaking change",
+                result: Some(Value::test_record(record!(
+                    "message" => Value::test_string("feat(api)!: remove the old API"),
+                    "type" => Value::test_string("feat"),
+                    "scope" => Value::test_string("api"),
+                    "breaking" => Value::test_bool(true),
+                    "description" => Value::test_string("remove the old API"),
+                ))),
+            },
+            Example {
+                example: r#"([[message author]; ["fix: handle empty input" alice]] | semver commits classify).commits.0.type"#,
+                description: "Classify the commits of a table",
+                result: Some(Value::test_string("fix")),
+            },
+            Example {
+                example: r#"([[message type]; ["fix: typo" chore]] | semver commits classify).commits.0"#,
+                description: "Replace the columns of commits classified before",
+                result: Some(Value::test_record(record!(
+                    "message" => Value::test_string("fix: typo"),
+                    "type" => Value::test_string("fix"),
+                    "scope" => Value::test_nothing(),
+                    "breaking" => Value::test_bool(false),
+                    "description" => Value::test_string("typo"),
+                ))),
+            },
+            Example {
+                example: r#""1.2.3" | semver bump (["feat: add ranges", "fix: typo"] | semver commits classify).level"#,
+                description: "Bump a version according to a list of commits",
+                result: Some(SemverCustomValue::test_value("1.3.0")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let column = call
+            .get_flag::<Spanned<String>>("column")?
+            .unwrap_or_else(|| Spanned {
+                item: "message".to_owned(),
+                span,
+            });
+
+        let mut commits = vec![];
+        let mut rows = vec![];
+        for value in input.as_list()? {
+            let (mut row, message) = match value {
+                Value::Record { val, .. } => {
+                    let message = get_column(value, &column)?.as_str()?.to_owned();
+                    (val.clone().into_owned(), message)
+                }
+                _ => {
+                    let message = value.as_str()?.to_owned();
+                    (
+                        record!("message" => Value::string(&message, value.span())),
+                        message,
+                    )
+                }
+            };
+            let commit = ConventionalCommit::parse(&message);
+            add_columns(&mut row, commit.as_ref(), span);
+            rows.push(Value::record(row, span));
+            commits.extend(commit);
+        }
+
+        let level = conventional::summary_level(&commits);
+        Ok(Value::record(
+            record!(
+                "commits" => Value::list(rows, span),
+                "level" => level.map_or(Value::nothing(span), |l| Value::string(l.to_string(), span)),
+            ),
+            span,
+        ))
+    }
+}
+
+fn add_columns(row: &mut Record, commit: Option<&ConventionalCommit>, span: Span) {
+    let string = |s: Option<&String>| s.map_or(Value::nothing(span), |s| Value::string(s, span));
+    row.insert("type", string(commit.map(|c| &c.kind)));
+    row.insert("scope", string(commit.and_then(|c| c.scope.as_ref())));
+    row.insert(
+        "breaking",
+        Value::bool(commit.is_some_and(|c| c.breaking), span),
+    );
+    row.insert("description", string(commit.map(|c| &c.description)));
+}
+
+fn classify_type() -> Type {
+    Type::Record(
+        vec![
+            ("commits".to_string(), Type::table()),
+            ("level".to_string(), Type::String),
+        ]
+        .into(),
+    )
+}
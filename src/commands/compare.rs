@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+
+use nu_plugin::{EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Example, LabeledError, Signature, SyntaxShape, Type, Value};
+
+use crate::{
+    SemverPlugin,
+    config::SemverConfig,
+    scheme::{
+        DebScheme, GoScheme, MavenScheme, Pep440Scheme, RpmScheme, Scheme, SemverScheme,
+        VersioningScheme,
+    },
+};
+
+use super::{custom_type, get_scheme, pep440_custom_type, scheme_flag};
+
+pub struct SemverCompare;
+
+impl SimplePluginCommand for SemverCompare {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver compare"
+    }
+
+    fn description(&self) -> &str {
+        "Compare two versions, returning -1, 0 or 1 if the input is lower, equal or greater"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Versions are compared using SemVer ordering, or the ordering of the versioning scheme given with `--scheme`. The `deb` and `rpm` schemes follow the algorithms of `dpkg --compare-versions` and `rpmvercmp`, and the `maven` scheme follows Maven's `ComparableVersion`."
+    }
+
+    fn signature(&self) -> Signature {
+        scheme_flag(Signature::build(self.name()))
+            .required(
+                "other",
+                SyntaxShape::Any,
+                "The version to compare to, either as a string or a version value",
+            )
+            .input_output_types(vec![
+                (Type::String, Type::Int),
+                (custom_type(), Type::Int),
+                (pep440_custom_type(), Type::Int),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""1.2.10" | semver compare "1.2.9""#,
+                description: "Compare two SemVer versions",
+                result: Some(Value::test_int(1)),
+            },
+            Example {
+                example: r#""1.0~rc1-1" | semver compare --scheme deb "1.0-1""#,
+                description: "Compare two Debian versions, where `~` sorts before the release",
+                result: Some(Value::test_int(-1)),
+            },
+            Example {
+                example: r#""1:1.0-1.el9" | semver compare --scheme rpm "2.0-1.el9""#,
+                description: "Compare two RPM versions, where the epoch takes precedence",
+                result: Some(Value::test_int(1)),
+            },
+            Example {
+                example: r#""2.3.4" | semver compare --scheme rpm "2.3.4-1.el9""#,
+                description: "Compare RPM versions, where a version without a release matches any release",
+                result: Some(Value::test_int(0)),
+            },
+            Example {
+                example: r#""1.0.0.Final" | semver compare --scheme maven "1""#,
+                description: "Compare two Maven versions, where release qualifiers and trailing zeros are ignored",
+                result: Some(Value::test_int(0)),
+            },
+            Example {
+                example: r#""1.0.0" | semver compare --scheme pep440 "1.0""#,
+                description: "Compare two Python versions",
+                result: Some(Value::test_int(0)),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let other: Value = call.req(0)?;
+        let config = SemverConfig::from_engine(engine)?;
+        let semver = SemverScheme {
+            config: &config,
+            lenient: config.lenient,
+        };
+        let ordering = match get_scheme(call)? {
+            Scheme::Semver => semver.compare_values(input, &other)?,
+            Scheme::Pep440 => Pep440Scheme.compare_values(input, &other)?,
+            Scheme::Deb => DebScheme.compare_values(input, &other)?,
+            Scheme::Rpm => RpmScheme.compare_values(input, &other)?,
+            Scheme::Go => GoScheme.compare_values(input, &other)?,
+            Scheme::Maven => MavenScheme.compare_values(input, &other)?,
+        };
+        let result = match ordering {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        };
+        Ok(Value::int(result, call.head))
+    }
+}
@@ -0,0 +1,114 @@
+use nu_plugin::{EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Example, LabeledError, Record, Signature, SyntaxShape, Type, Value, record};
+
+use crate::{SemverPlugin, config::SemverConfig, custom_value::SemverCustomValue, version::Change};
+
+use super::custom_type;
+
+pub struct SemverDiff;
+
+impl SimplePluginCommand for SemverDiff {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver diff"
+    }
+
+    fn description(&self) -> &str {
+        "Classify the change between two versions"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The level is one of major, minor, patch, premajor, preminor, prepatch, prerelease, build or none, and follows the same rules as npm's `semver.diff`. The distance of each component is computed from the input version to the other version."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "other",
+                SyntaxShape::Any,
+                "The version to compare to, either as a string or a semver value",
+            )
+            .input_output_types(vec![
+                (Type::String, diff_type()),
+                (custom_type(), diff_type()),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""1.2.3" | semver diff "1.4.0""#,
+                description: "Compare two release versions",
+                result: Some(Value::test_record(record!(
+                    "level" => Value::test_string("minor"),
+                    "major" => Value::test_int(0),
+                    "minor" => Value::test_int(2),
+                    "patch" => Value::test_int(-3),
+                ))),
+            },
+            Example {
+                example: r#"("1.2.3" | semver diff "2.0.0-rc.1").level"#,
+                description: "Moving to a pre-release of the next major version",
+                result: Some(Value::test_string("premajor")),
+            },
+            Example {
+                example: r#"("1.2.3-alpha.1" | semver diff ("1.2.3-alpha.1" | semver bump patch)).level"#,
+                description: "Diffing a bumped version reports the level it was bumped at",
+                result: Some(Value::test_string("patch")),
+            },
+            Example {
+                example: r#"("1.0.0-rc.1" | semver diff ("1.0.0-rc.1" | semver bump minor)).level"#,
+                description: "Bumping the minor level of a pre-release moves to the next minor version",
+                result: Some(Value::test_string("minor")),
+            },
+            Example {
+                example: r#"("1.0.0-alpha.1" | semver diff ("1.0.0-alpha.1" | semver bump patch)).level"#,
+                description: "Bumping the patch level of a pre-release releases it",
+                result: Some(Value::test_string("patch")),
+            },
+            Example {
+                example: r#"("1.2.0-rc.1" | semver diff ("1.2.0-rc.1" | semver bump patch)).level"#,
+                description: "Releasing a pre-release is a patch change, whatever its version",
+                result: Some(Value::test_string("patch")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let config = SemverConfig::from_engine(engine)?;
+        let from = SemverCustomValue::try_from_value(input, config.lenient)?;
+        let to = SemverCustomValue::try_from_value(&call.req::<Value>(0)?, config.lenient)?;
+
+        let distance = |a: u64, b: u64| Value::int(b as i64 - a as i64, span);
+        let mut record = Record::new();
+        record.push(
+            "level",
+            Value::string(Change::between(&from, &to).to_string(), span),
+        );
+        record.push("major", distance(from.major, to.major));
+        record.push("minor", distance(from.minor, to.minor));
+        record.push("patch", distance(from.patch, to.patch));
+
+        Ok(Value::record(record, span))
+    }
+}
+
+fn diff_type() -> Type {
+    Type::Record(
+        vec![
+            ("level".to_string(), Type::String),
+            ("major".to_string(), Type::Int),
+            ("minor".to_string(), Type::Int),
+            ("patch".to_string(), Type::Int),
+        ]
+        .into(),
+    )
+}
@@ -0,0 +1,191 @@
+use std::path::PathBuf;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Example, LabeledError, Signature, Spanned, SyntaxShape, Type, Value, record};
+
+use crate::{
+    SemverPlugin,
+    config::SemverConfig,
+    custom_value::SemverCustomValue,
+    git::{Repository, Tag},
+};
+
+use super::custom_type;
+
+pub struct SemverGitNext;
+pub struct SemverGitTags;
+
+impl SimplePluginCommand for SemverGitNext {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver git next"
+    }
+
+    fn description(&self) -> &str {
+        "Compute the next version of a git repository from its tags and Conventional Commits"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The latest version tag reachable from HEAD is bumped according to the commits since then: breaking changes (`!` or a `BREAKING CHANGE` footer) bump the major version, `feat` commits the minor version, and `fix` commits the patch version. While the major version is 0, breaking changes only bump the minor version. If no commit requires a bump, the latest version is returned, and if there is no version tag, `0.0.0` is bumped. This runs the `git` command."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional(
+                "path",
+                SyntaxShape::Directory,
+                "The repository (defaults to the current directory)",
+            )
+            .named(
+                "prefix",
+                SyntaxShape::String,
+                "The prefix of version tags, e.g. `v` or `my-crate-v` (by default, an optional `v`)",
+                Some('p'),
+            )
+            .input_output_types(vec![(Type::Nothing, custom_type())])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "semver git next",
+                description: "Get the next version of the repository in the current directory",
+                result: None,
+            },
+            Example {
+                example: "semver git next crates/my-crate --prefix my-crate-v",
+                description: "Get the next version of a crate, whose tags are prefixed with its name",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let config = SemverConfig::from_engine(engine)?;
+        let repo = open(engine, call)?;
+        let prefix: Option<String> = call.get_flag("prefix")?;
+        let version = repo
+            .next_version(prefix.as_deref(), &config.prerelease_labels)
+            .map_err(|e| e.into_labeled_error(span))?;
+        Ok(SemverCustomValue(version).into_value(span))
+    }
+}
+
+impl SimplePluginCommand for SemverGitTags {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver git tags"
+    }
+
+    fn description(&self) -> &str {
+        "List the version tags of a git repository, sorted by SemVer precedence"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Tags that aren't SemVer versions (once the prefix is removed) are skipped, unless `--include-invalid` is set: they are then listed last, with an empty version. This runs the `git` command."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional(
+                "path",
+                SyntaxShape::Directory,
+                "The repository (defaults to the current directory)",
+            )
+            .named(
+                "prefix",
+                SyntaxShape::String,
+                "The prefix of version tags, e.g. `v` or `my-crate-v` (by default, an optional `v`)",
+                Some('p'),
+            )
+            .switch(
+                "include-invalid",
+                "Also list the tags that aren't versions",
+                Some('i'),
+            )
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table(
+                    vec![
+                        ("tag".to_string(), Type::String),
+                        ("version".to_string(), custom_type()),
+                        ("commit".to_string(), Type::String),
+                        ("date".to_string(), Type::Date),
+                    ]
+                    .into(),
+                ),
+            )])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "semver git tags --prefix v",
+                description: "List the version tags of the repository in the current directory",
+                result: None,
+            },
+            Example {
+                example: "semver git tags | last",
+                description: "Get the tag of the highest version",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let repo = open(engine, call)?;
+        let prefix: Option<String> = call.get_flag("prefix")?;
+        let prefix = prefix.as_deref();
+
+        let row = |tag: Tag, version: Option<semver::Version>| {
+            Value::record(
+                record!(
+                    "tag" => Value::string(tag.name, span),
+                    "version" => version.map_or(Value::nothing(span), |v| SemverCustomValue(v).into_value(span)),
+                    "commit" => Value::string(tag.commit, span),
+                    "date" => tag.date.map_or(Value::nothing(span), |d| Value::date(d, span)),
+                ),
+                span,
+            )
+        };
+        let mut rows: Vec<Value> = repo
+            .version_tags(prefix, false)
+            .map_err(|e| e.into_labeled_error(span))?
+            .into_iter()
+            .map(|(version, tag)| row(tag, Some(version)))
+            .collect();
+        if call.has_flag("include-invalid")? {
+            let tags = repo.tags(false).map_err(|e| e.into_labeled_error(span))?;
+            rows.extend(
+                tags.into_iter()
+                    .filter(|tag| tag.version(prefix).is_none())
+                    .map(|tag| row(tag, None)),
+            );
+        }
+        Ok(Value::list(rows, span))
+    }
+}
+
+/// Open the repository given as the first argument, or the one of the current directory.
+fn open(engine: &EngineInterface, call: &EvaluatedCall) -> Result<Repository, LabeledError> {
+    let path: Option<Spanned<String>> = call.opt(0)?;
+    let dir = PathBuf::from(engine.get_current_dir()?)
+        .join(path.as_ref().map_or("", |p| p.item.as_str()));
+    Repository::open(&dir).map_err(|e| e.into_labeled_error(path.map_or(call.head, |p| p.span)))
+}
@@ -0,0 +1,126 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Example, LabeledError, Record, Signature, Type, Value, record};
+
+use crate::{
+    SemverPlugin,
+    custom_value::SemverCustomValue,
+    go::{self, PseudoVersion},
+};
+
+use super::custom_type;
+
+pub struct SemverGoParse;
+
+impl SimplePluginCommand for SemverGoParse {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver go parse"
+    }
+
+    fn description(&self) -> &str {
+        "Parse a Go module version, splitting pseudo-versions into their parts"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Go versions need the `v` prefix, and can use the shorthands `v1` and `v1.2`. For pseudo-versions (e.g. `v0.0.0-20191109021931-daa7c04131f5`), the base version, commit time and revision are extracted; they are empty for other versions."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).input_output_types(vec![(Type::String, go_type())])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""v1.2.4-0.20191109021931-daa7c04131f5" | semver go parse"#,
+                description: "Parse a pseudo-version based on a release",
+                result: Some(Value::test_record(record!(
+                    "version" => SemverCustomValue::test_value("1.2.4-0.20191109021931-daa7c04131f5"),
+                    "canonical" => Value::test_string("v1.2.4-0.20191109021931-daa7c04131f5"),
+                    "pseudo" => Value::test_bool(true),
+                    "base" => SemverCustomValue::test_value("1.2.3"),
+                    "time" => Value::test_string("2019-11-09T02:19:31Z"),
+                    "revision" => Value::test_string("daa7c04131f5"),
+                    "incompatible" => Value::test_bool(false),
+                ))),
+            },
+            Example {
+                example: r#"("v0.0.0-20191109021931-daa7c04131f5" | semver go parse).base"#,
+                description: "Pseudo-versions of modules without any tag have no base version",
+                result: Some(Value::test_nothing()),
+            },
+            Example {
+                example: r#"("v2.0.0+incompatible" | semver go parse).incompatible"#,
+                description: "Detect versions of modules that don't have a go.mod file",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                example: r#"("v1.2" | semver go parse).canonical"#,
+                description: "Get the canonical form of a shorthand version",
+                result: Some(Value::test_string("v1.2.0")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let version = go::parse(input.as_str()?).map_err(|e| e.into_labeled_error(input.span()))?;
+        let pseudo = PseudoVersion::from_version(&version);
+
+        let mut record = Record::new();
+        record.push(
+            "version",
+            SemverCustomValue(version.clone()).into_value(span),
+        );
+        record.push("canonical", Value::string(go::canonical(&version), span));
+        record.push("pseudo", Value::bool(pseudo.is_some(), span));
+        record.push(
+            "base",
+            pseudo
+                .as_ref()
+                .and_then(|p| p.base.clone())
+                .map_or(Value::nothing(span), |base| {
+                    SemverCustomValue(base).into_value(span)
+                }),
+        );
+        record.push(
+            "time",
+            pseudo
+                .as_ref()
+                .map_or(Value::nothing(span), |p| Value::string(p.time(), span)),
+        );
+        record.push(
+            "revision",
+            pseudo
+                .as_ref()
+                .map_or(Value::nothing(span), |p| Value::string(&p.revision, span)),
+        );
+        record.push(
+            "incompatible",
+            Value::bool(go::is_incompatible(&version), span),
+        );
+        Ok(Value::record(record, span))
+    }
+}
+
+fn go_type() -> Type {
+    Type::Record(
+        vec![
+            ("version".to_string(), custom_type()),
+            ("canonical".to_string(), Type::String),
+            ("pseudo".to_string(), Type::Bool),
+            ("base".to_string(), Type::Any),
+            ("time".to_string(), Type::Any),
+            ("revision".to_string(), Type::Any),
+            ("incompatible".to_string(), Type::Bool),
+        ]
+        .into(),
+    )
+}
@@ -0,0 +1,74 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Example, LabeledError, PipelineData, Signature, Type, Value};
+
+use crate::{SemverPlugin, calver::DEFAULT_FORMAT, custom_value::CalverCustomValue};
+
+use super::{calver_custom_type, calver_format_flag, get_calver_format};
+
+pub struct IntoCalver;
+
+impl PluginCommand for IntoCalver {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "into calver"
+    }
+
+    fn description(&self) -> &str {
+        "Build a calendar version (CalVer) value from a string following a format"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Formats are made of the segments YYYY, YY, 0Y, MM, 0M, WW, 0W, DD, 0D, MAJOR, MINOR and MICRO, separated by `.`, `-` or `_` (see https://calver.org). The date segments are checked, e.g. `2024.13.1` isn't a valid `YYYY.MM.MICRO` version."
+    }
+
+    fn signature(&self) -> Signature {
+        calver_format_flag(Signature::build(self.name())).input_output_types(vec![
+            (Type::String, calver_custom_type()),
+            (calver_custom_type(), calver_custom_type()),
+            (
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(calver_custom_type())),
+            ),
+        ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""2024.10.3" | into calver"#,
+                description: "Parse a version with the default format",
+                result: Some(CalverCustomValue::test_value("2024.10.3", DEFAULT_FORMAT)),
+            },
+            Example {
+                example: r#"("24.04.1" | into calver --format YY.0M.MICRO).year"#,
+                description: "Parse a version with a short year, and access its year",
+                result: Some(Value::test_int(2024)),
+            },
+            Example {
+                example: r#"("2024.02.1" | into calver) < ("2024.10.0" | into calver)"#,
+                description: "Compare calendar versions",
+                result: Some(Value::test_bool(true)),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let head = call.head;
+        let format = get_calver_format(call)?;
+
+        Ok(input.map(
+            move |v| match CalverCustomValue::try_from_value(&v, &format) {
+                Ok(version) => version.into_value(v.span()),
+                Err(e) => Value::error(e, head),
+            },
+            engine.signals(),
+        )?)
+    }
+}
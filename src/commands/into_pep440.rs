@@ -0,0 +1,97 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Example, LabeledError, PipelineData, ShellError, Signature, Type, Value};
+
+use crate::{
+    SemverPlugin,
+    custom_value::{Pep440CustomValue, SemverCustomValue},
+    pep440::Pep440Version,
+};
+
+use super::{custom_type, pep440_custom_type};
+
+pub struct IntoPep440;
+
+impl PluginCommand for IntoPep440 {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "into pep440"
+    }
+
+    fn description(&self) -> &str {
+        "Build a PEP 440 (Python) version value from a valid string, or from a semver value"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Versions are normalized, e.g. `1.0-ALPHA.1` becomes `1.0a1`. Semver values can only be converted if their pre-release, if any, is `alpha.N`, `beta.N` or `rc.N`; their build metadata becomes the local version label."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).input_output_types(vec![
+            (Type::String, pep440_custom_type()),
+            (custom_type(), pep440_custom_type()),
+            (pep440_custom_type(), pep440_custom_type()),
+            (
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(pep440_custom_type())),
+            ),
+        ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""1.0-ALPHA.1" | into pep440"#,
+                description: "Parse and normalize a PEP 440 version",
+                result: Some(Pep440CustomValue::test_value("1.0a1")),
+            },
+            Example {
+                example: r#"("2!1.0.post2.dev3+ubuntu.1" | into pep440).post"#,
+                description: "Access the post-release number of a version",
+                result: Some(Value::test_int(2)),
+            },
+            Example {
+                example: r#"("1.0.dev1" | into pep440) < ("1.0rc1" | into pep440)"#,
+                description: "Compare versions according to PEP 440, where dev-releases come first",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                example: r#""1.2.3-rc.1+build.5" | into semver | into pep440"#,
+                description: "Convert a semver value into a PEP 440 version",
+                result: Some(Pep440CustomValue::test_value("1.2.3rc1+build.5")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let head = call.head;
+
+        Ok(input.map(
+            move |v| convert(&v).unwrap_or_else(|e| Value::error(e, head)),
+            engine.signals(),
+        )?)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn convert(input: &Value) -> Result<Value, ShellError> {
+    let span = input.span();
+    if let Ok(cv) = input.as_custom_value()
+        && let Some(semver) = cv.as_any().downcast_ref::<SemverCustomValue>()
+    {
+        let version =
+            Pep440Version::from_semver(semver).map_err(|e| ShellError::IncorrectValue {
+                msg: e.to_string(),
+                val_span: span,
+                call_span: span,
+            })?;
+        return Ok(Pep440CustomValue(version).into_value(span));
+    }
+    Ok(Pep440CustomValue::try_from(input)?.into_value(span))
+}
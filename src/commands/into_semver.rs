@@ -1,13 +1,16 @@
-use nu_plugin::SimplePluginCommand;
-use nu_protocol::{LabeledError, Signature, Type, Value};
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Example, LabeledError, PipelineData, ShellError, Signature, SyntaxShape, Type, Value,
+    ast::CellPath, record,
+};
 
-use crate::{SemverPlugin, custom_value::SemverCustomValue};
+use crate::{SemverPlugin, config::SemverConfig, custom_value::SemverCustomValue};
 
 use super::custom_type;
 
 pub struct IntoSemver;
 
-impl SimplePluginCommand for IntoSemver {
+impl PluginCommand for IntoSemver {
     type Plugin = SemverPlugin;
 
     fn name(&self) -> &str {
@@ -19,37 +22,146 @@ impl SimplePluginCommand for IntoSemver {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build(self.name()).input_output_types(vec![
-            (Type::String, custom_type()),
-            (
-                Type::List(Box::new(Type::String)),
-                Type::List(Box::new(custom_type())),
-            ),
-        ])
+        Signature::build(self.name())
+            .switch(
+                "lenient",
+                "Coerce strings that aren't valid SemVer versions (e.g. `v1.2`) instead of failing",
+                Some('l'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, convert data at the given cell paths",
+            )
+            .input_output_types(vec![
+                (Type::String, custom_type()),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(custom_type())),
+                ),
+                (Type::table(), Type::table()),
+                (Type::record(), Type::record()),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""1.2.3" | into semver"#,
+                description: "Convert a string into a semver value",
+                result: Some(SemverCustomValue::test_value("1.2.3")),
+            },
+            Example {
+                example: r#"{ name: "foo", version: "1.2.3" } | into semver version"#,
+                description: "Convert a field of a record into a semver value",
+                result: Some(Value::test_record(record!(
+                    "name" => Value::test_string("foo"),
+                    "version" => SemverCustomValue::test_value("1.2.3"),
+                ))),
+            },
+            Example {
+                example: r#"[[name version]; [foo "1.2.3"] [bar "0.1.0"]] | into semver version"#,
+                description: "Convert a column of a table into semver values",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record!(
+                        "name" => Value::test_string("foo"),
+                        "version" => SemverCustomValue::test_value("1.2.3"),
+                    )),
+                    Value::test_record(record!(
+                        "name" => Value::test_string("bar"),
+                        "version" => SemverCustomValue::test_value("0.1.0"),
+                    )),
+                ])),
+            },
+            Example {
+                example: r#"["v1.2", "release-3.4.5", "1.2.3.4"] | into semver --lenient"#,
+                description: "Coerce loosely formatted versions into semver values",
+                result: Some(Value::test_list(vec![
+                    SemverCustomValue::test_value("1.2.0"),
+                    SemverCustomValue::test_value("3.4.5"),
+                    SemverCustomValue::test_value("1.2.3"),
+                ])),
+            },
+            Example {
+                example: r#"("1.2.3" | into semver) < ("1.10.0" | into semver)"#,
+                description: "Compare semver values",
+                result: Some(Value::test_bool(true)),
+            },
+        ]
     }
 
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &nu_plugin::EngineInterface,
-        _call: &nu_plugin::EvaluatedCall,
-        input: &nu_protocol::Value,
-    ) -> Result<nu_protocol::Value, LabeledError> {
-        // TODO: support table/records with a cellpath (like `into datetime`)
-        match input {
-            Value::String { internal_span, .. } => {
-                let v: SemverCustomValue = input.try_into()?;
-                Ok(v.into_value(*internal_span))
-            }
-            list_val @ Value::List { vals, .. } => {
-                let span = list_val.span();
-                let semvers = vals
-                    .iter()
-                    .map(|v| SemverCustomValue::try_from(v).map(|v| v.into_value(span)))
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(Value::list(semvers, span))
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let head = call.head;
+        let cell_paths: Vec<CellPath> = call.rest(0)?;
+        let lenient = call.has_flag("lenient")? || SemverConfig::from_engine(engine)?.lenient;
+
+        // Lists and streams are converted element by element, so streams stay lazy.
+        Ok(input.map(
+            move |v| {
+                let res = if cell_paths.is_empty() {
+                    convert(&v, lenient)
+                } else {
+                    convert_cell_paths(&v, &cell_paths, lenient)
+                };
+                res.unwrap_or_else(|e| Value::error(e, head))
+            },
+            engine.signals(),
+        )?)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn convert(input: &Value, lenient: bool) -> Result<Value, ShellError> {
+    match input {
+        list_val @ Value::List { vals, .. } => {
+            let span = list_val.span();
+            let semvers = vals
+                .iter()
+                .map(|v| SemverCustomValue::try_from_value(v, lenient).map(|v| v.into_value(span)))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::list(semvers, span))
+        }
+        _ => {
+            let v = SemverCustomValue::try_from_value(input, lenient)?;
+            Ok(v.into_value(input.span()))
+        }
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn convert_cell_paths(
+    input: &Value,
+    cell_paths: &[CellPath],
+    lenient: bool,
+) -> Result<Value, ShellError> {
+    match input {
+        // Tables are converted row by row, so that each cell path is resolved against a single
+        // record rather than against the whole column.
+        Value::List { vals, .. } => {
+            let rows = vals
+                .iter()
+                .map(|row| convert_cell_paths(row, cell_paths, lenient))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::list(rows, input.span()))
+        }
+        _ => {
+            let mut ret = input.clone();
+            for path in cell_paths {
+                ret.update_cell_path(
+                    &path.members,
+                    Box::new(|old| match convert(old, lenient) {
+                        Ok(v) => v,
+                        Err(e) => Value::error(e, old.span()),
+                    }),
+                )?;
             }
-            _ => todo!(),
+            Ok(ret)
         }
     }
 }
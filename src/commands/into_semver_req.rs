@@ -0,0 +1,58 @@
+use nu_plugin::SimplePluginCommand;
+use nu_protocol::{Example, LabeledError, Signature, Type, Value};
+
+use crate::{SemverPlugin, custom_value::SemverReqCustomValue, version::VersionReqValue};
+
+use super::{dialect_flag, get_dialect, req_custom_type};
+
+pub struct IntoSemverReq;
+
+impl SimplePluginCommand for IntoSemverReq {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "into semver-req"
+    }
+
+    fn description(&self) -> &str {
+        "Build a semver requirement value from a valid requirement string"
+    }
+
+    fn signature(&self) -> Signature {
+        dialect_flag(Signature::build(self.name())).input_output_types(vec![
+            (Type::String, req_custom_type()),
+            (req_custom_type(), req_custom_type()),
+        ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"">=1.2.3, <2" | into semver-req"#,
+                description: "Parse a version requirement",
+                result: Some(SemverReqCustomValue::test_value(">=1.2.3, <2")),
+            },
+            Example {
+                example: r#"("^1.2" | into semver-req).comparators.0.op"#,
+                description: "Access the comparators of a version requirement",
+                result: Some(Value::test_string("^")),
+            },
+            Example {
+                example: r#""1.2.x || >=2.0.0 <2.5.0" | into semver-req --dialect npm"#,
+                description: "Parse an npm range, made of several alternatives",
+                result: Some(SemverReqCustomValue::test_value("1.2.* || >=2.0.0, <2.5.0")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        _engine: &nu_plugin::EngineInterface,
+        call: &nu_plugin::EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let req = VersionReqValue::try_from_value(input, get_dialect(call)?)?;
+        Ok(req.into_value())
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::PathBuf;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Example, LabeledError, ShellError, Signature, Spanned, SyntaxShape, Type, Value, record,
+};
+
+use crate::{
+    SemverPlugin, config::SemverConfig, custom_value::SemverCustomValue, manifest::Manifest,
+};
+
+use super::custom_type;
+
+pub struct SemverManifestGet;
+pub struct SemverManifestSet;
+
+impl SimplePluginCommand for SemverManifestGet {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver manifest get"
+    }
+
+    fn description(&self) -> &str {
+        "Read the version of a Cargo.toml, package.json or pyproject.toml manifest"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The path can be either the manifest itself, or the directory containing it. For Cargo packages with `version.workspace = true`, the version is read from `[workspace.package]` in the workspace root. For Python projects, `[project]` and `[tool.poetry]` are supported."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "path",
+                SyntaxShape::Filepath,
+                "The manifest, or the directory containing it",
+            )
+            .switch(
+                "lenient",
+                "Coerce versions that aren't valid SemVer (e.g. `1.2`) instead of failing",
+                Some('l'),
+            )
+            .input_output_types(vec![(Type::Nothing, custom_type())])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "semver manifest get Cargo.toml",
+                description: "Get the version of a Rust crate",
+                result: None,
+            },
+            Example {
+                example: "semver manifest get . | semver bump minor",
+                description: "Get the next minor version of the project in the current directory",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let path: Spanned<String> = call.req(0)?;
+        let lenient = call.has_flag("lenient")? || SemverConfig::from_engine(engine)?.lenient;
+        let manifest = open(engine, &path)?;
+        let version = manifest
+            .version()
+            .map_err(|e| e.into_labeled_error(path.span))?;
+        let version =
+            SemverCustomValue::try_from_value(&Value::string(version, path.span), lenient)?;
+        Ok(version.into_value(call.head))
+    }
+}
+
+impl SimplePluginCommand for SemverManifestSet {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver manifest set"
+    }
+
+    fn description(&self) -> &str {
+        "Set the version of a Cargo.toml, package.json or pyproject.toml manifest"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Only the version field is modified: formatting, comments and key order are preserved. For Cargo packages with `version.workspace = true`, the version is set in `[workspace.package]` of the workspace root. The version can be given as an argument, or through the pipeline. Returns the edited file, with its previous and new versions."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "path",
+                SyntaxShape::Filepath,
+                "The manifest, or the directory containing it",
+            )
+            .optional(
+                "version",
+                SyntaxShape::Any,
+                "The new version, either as a string or a semver value (defaults to the input)",
+            )
+            .input_output_types(vec![
+                (Type::Nothing, edit_type()),
+                (Type::String, edit_type()),
+                (custom_type(), edit_type()),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"semver manifest set package.json "2.0.0""#,
+                description: "Set the version of an npm package",
+                result: None,
+            },
+            Example {
+                example: "semver manifest get Cargo.toml | semver bump patch | semver manifest set Cargo.toml",
+                description: "Bump the version of a Rust crate (or of its workspace, if the version is inherited)",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let path: Spanned<String> = call.req(0)?;
+        let version = match call.opt::<Value>(1)? {
+            Some(version) => version,
+            None if !input.is_nothing() => input.clone(),
+            None => {
+                return Err(LabeledError::new("Missing version").with_label(
+                    "a version should be given as an argument or as input",
+                    call.head,
+                ));
+            }
+        };
+        let SemverCustomValue(version) = SemverCustomValue::try_from_value(&version, false)?;
+
+        let mut manifest = open(engine, &path)?;
+        let span = path.span;
+        let previous = manifest.version().map_err(|e| e.into_labeled_error(span))?;
+        manifest
+            .set_version(&version.to_string())
+            .and_then(|()| manifest.save())
+            .map_err(|e| e.into_labeled_error(span))?;
+
+        Ok(Value::record(
+            record!(
+                "path" => Value::string(manifest.path.display().to_string(), call.head),
+                "previous" => Value::string(previous, call.head),
+                "version" => SemverCustomValue(version).into_value(call.head),
+            ),
+            call.head,
+        ))
+    }
+}
+
+/// Open the manifest at the given path (relative to the current directory), following workspace
+/// inheritance.
+#[allow(clippy::result_large_err)]
+fn open(engine: &EngineInterface, path: &Spanned<String>) -> Result<Manifest, ShellError> {
+    let full_path = PathBuf::from(engine.get_current_dir()?).join(&path.item);
+    Manifest::open(&full_path)
+        .and_then(Manifest::version_source)
+        .map_err(|e| ShellError::IncorrectValue {
+            msg: e.to_string(),
+            val_span: path.span,
+            call_span: path.span,
+        })
+}
+
+fn edit_type() -> Type {
+    Type::Record(
+        vec![
+            ("path".to_string(), Type::String),
+            ("previous".to_string(), Type::String),
+            ("version".to_string(), custom_type()),
+        ]
+        .into(),
+    )
+}
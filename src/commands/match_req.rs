@@ -1,13 +1,15 @@
-use nu_plugin::{EvaluatedCall, SimplePluginCommand};
-use nu_protocol::{Example, LabeledError, Signature, SyntaxShape, Type, Value};
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{Example, LabeledError, PipelineData, Signature, SyntaxShape, Type, Value};
 
-use crate::{SemverPlugin, custom_value::SemverCustomValue, version::VersionReqValue};
+use crate::{
+    SemverPlugin, config::SemverConfig, custom_value::SemverCustomValue, version::VersionReqValue,
+};
 
-use super::custom_type;
+use super::{custom_type, dialect_flag, get_dialect};
 
 pub struct SemverMatchReq;
 
-impl SimplePluginCommand for SemverMatchReq {
+impl PluginCommand for SemverMatchReq {
     type Plugin = SemverPlugin;
 
     fn name(&self) -> &str {
@@ -19,16 +21,29 @@ impl SimplePluginCommand for SemverMatchReq {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build(self.name())
+        dialect_flag(Signature::build(self.name()))
             .required(
                 "requirement",
-                SyntaxShape::String,
-                "A valid version requirement",
+                SyntaxShape::Any,
+                "A valid version requirement, either as a string or a `semver-req` value",
+            )
+            .switch(
+                "lenient",
+                "Coerce versions that aren't valid SemVer (e.g. `v1.2`) instead of failing",
+                Some('l'),
             )
             .filter()
             .input_output_types(vec![
                 (Type::String, Type::Bool),
                 (custom_type(), Type::Bool),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::Bool)),
+                ),
+                (
+                    Type::List(Box::new(custom_type())),
+                    Type::List(Box::new(Type::Bool)),
+                ),
             ])
     }
 
@@ -49,20 +64,60 @@ impl SimplePluginCommand for SemverMatchReq {
                 description: "Match a SemVer version against a version requirement.",
                 result: Some(Value::test_bool(false)),
             },
+            Example {
+                example: r#" ["1.9.0", "2.0.0", "2.5.1", "3.0.0"] | semver match-req "^2" "#,
+                description: "Match every version of a list against a version requirement.",
+                result: Some(Value::test_list(vec![
+                    Value::test_bool(false),
+                    Value::test_bool(true),
+                    Value::test_bool(true),
+                    Value::test_bool(false),
+                ])),
+            },
+            Example {
+                example: r#" "v2.1" | semver match-req --lenient "^2" "#,
+                description: "Match a loosely formatted version against a version requirement.",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                example: r#"let req = ">=2,<3" | into semver-req; "2.4.0" | semver match-req $req"#,
+                description: "Match a SemVer version against a pre-parsed version requirement.",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                example: r#" "1.5.0" | semver match-req --dialect npm "1.2.3 - 2.3.4 || >=3" "#,
+                description: "Match a SemVer version against an npm range.",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                example: r#" ["1.2.0", "1.3.0", "2.0.0"] | semver match-req --dialect npm "1.2.x || 2" "#,
+                description: "Match versions against an npm range made of x-ranges.",
+                result: Some(Value::test_list(vec![
+                    Value::test_bool(true),
+                    Value::test_bool(false),
+                    Value::test_bool(true),
+                ])),
+            },
         ]
     }
 
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &nu_plugin::EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
-        input: &Value,
-    ) -> Result<Value, LabeledError> {
-        let req: VersionReqValue = call.req(0)?;
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let req = VersionReqValue::try_from_value(&call.req(0)?, get_dialect(call)?)?;
+        let lenient = call.has_flag("lenient")? || SemverConfig::from_engine(engine)?.lenient;
         let span = call.head;
-        let version: SemverCustomValue = input.try_into()?;
 
-        Ok(Value::bool(req.matches(&version), span))
+        Ok(input.map(
+            move |v| match SemverCustomValue::try_from_value(&v, lenient) {
+                Ok(version) => Value::bool(req.matches(&version, false), span),
+                Err(e) => Value::error(e, span),
+            },
+            engine.signals(),
+        )?)
     }
 }
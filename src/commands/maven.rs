@@ -0,0 +1,95 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Example, LabeledError, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type, Value,
+};
+
+use crate::{
+    SemverPlugin,
+    maven::{MavenRange, MavenVersion},
+};
+
+pub struct SemverMavenMatch;
+
+impl PluginCommand for SemverMavenMatch {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver maven match"
+    }
+
+    fn description(&self) -> &str {
+        "Match a Maven version against a version range, e.g. `[1.0,2.0)`"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Versions are compared like Maven's `ComparableVersion`. A range is made of one or more comma-separated restrictions, e.g. `(,1.0],[1.2,)`, and a bare version like `1.0` matches that version or any later one."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required("range", SyntaxShape::String, "A Maven version range")
+            .filter()
+            .input_output_types(vec![
+                (Type::String, Type::Bool),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::Bool)),
+                ),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"["1.0-SNAPSHOT", "1.0", "1.5.2", "2.0"] | semver maven match "[1.0,2.0)""#,
+                description: "Match versions against a half-open range",
+                result: Some(Value::test_list(vec![
+                    Value::test_bool(false),
+                    Value::test_bool(true),
+                    Value::test_bool(true),
+                    Value::test_bool(false),
+                ])),
+            },
+            Example {
+                example: r#""1.5.1" | semver maven match "(,1.5]""#,
+                description: "Match a version against an upper bound",
+                result: Some(Value::test_bool(false)),
+            },
+            Example {
+                example: r#""3.1" | semver maven match "(,1.0],[3.0,)""#,
+                description: "Match a version against a range made of several restrictions",
+                result: Some(Value::test_bool(true)),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let range: Spanned<String> = call.req(0)?;
+        let range = MavenRange::parse(&range.item).map_err(|e| e.into_labeled_error(range.span))?;
+        let span = call.head;
+
+        Ok(input.map(
+            move |v| match parse_version(&v) {
+                Ok(version) => Value::bool(range.matches(&version), span),
+                Err(e) => Value::error(e, span),
+            },
+            engine.signals(),
+        )?)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_version(value: &Value) -> Result<MavenVersion, ShellError> {
+    let span = value.span();
+    MavenVersion::parse(value.as_str()?).map_err(|e| ShellError::IncorrectValue {
+        msg: e.to_string(),
+        val_span: span,
+        call_span: span,
+    })
+}
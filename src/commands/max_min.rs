@@ -0,0 +1,275 @@
+use std::cmp::Ordering;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Example, IntoPipelineData, LabeledError, PipelineData, Signature, Spanned, SyntaxShape, Type,
+    Value, record,
+};
+
+use crate::{
+    SemverPlugin, config::SemverConfig, custom_value::SemverCustomValue, version::VersionReqValue,
+};
+
+use super::{custom_type, dialect_flag, get_column, get_dialect};
+
+pub struct SemverMax;
+pub struct SemverMin;
+pub struct SemverMaxSatisfying;
+pub struct SemverMinSatisfying;
+
+impl PluginCommand for SemverMax {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver max"
+    }
+
+    fn description(&self) -> &str {
+        "Return the highest version of a list"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Pre-release versions are ignored unless `--include-prerelease` is set. Returns nothing if there is no candidate."
+    }
+
+    fn signature(&self) -> Signature {
+        signature(self.name(), false)
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"["1.2.3", "1.10.0", "2.0.0-rc.1"] | semver max"#,
+                description: "Find the highest release version",
+                result: Some(Value::test_string("1.10.0")),
+            },
+            Example {
+                example: r#"["1.2.3", "1.10.0", "2.0.0-rc.1"] | semver max --include-prerelease"#,
+                description: "Find the highest version, including pre-releases",
+                result: Some(Value::test_string("2.0.0-rc.1")),
+            },
+            Example {
+                example: r#"[[name version]; [foo "1.10.0"] [bar "1.2.0"]] | semver max --by version"#,
+                description: "Find the row of a table with the highest version",
+                result: Some(Value::test_record(record!(
+                    "name" => Value::test_string("foo"),
+                    "version" => Value::test_string("1.10.0"),
+                ))),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        find_extremum(engine, call, input, None, Ordering::Greater)
+    }
+}
+
+impl PluginCommand for SemverMin {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver min"
+    }
+
+    fn description(&self) -> &str {
+        "Return the lowest version of a list"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Pre-release versions are ignored unless `--include-prerelease` is set. Returns nothing if there is no candidate."
+    }
+
+    fn signature(&self) -> Signature {
+        signature(self.name(), false)
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"["1.2.3", "1.10.0", "1.0.0-rc.1"] | semver min"#,
+                description: "Find the lowest release version",
+                result: Some(Value::test_string("1.2.3")),
+            },
+            Example {
+                example: r#"["1.2.3", "1.10.0", "1.0.0-rc.1"] | into semver | semver min --include-prerelease"#,
+                description: "Find the lowest version, including pre-releases",
+                result: Some(SemverCustomValue::test_value("1.0.0-rc.1")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        find_extremum(engine, call, input, None, Ordering::Less)
+    }
+}
+
+impl PluginCommand for SemverMaxSatisfying {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver max-satisfying"
+    }
+
+    fn description(&self) -> &str {
+        "Return the highest version of a list that satisfies a version requirement"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Pre-release versions only match if the requirement explicitly mentions a pre-release of the same version, unless `--include-prerelease` is set. Returns nothing if no version matches."
+    }
+
+    fn signature(&self) -> Signature {
+        signature(self.name(), true)
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"["1.2.3", "1.10.0", "2.0.0"] | semver max-satisfying "^1""#,
+                description: "Find the highest version matching a requirement",
+                result: Some(Value::test_string("1.10.0")),
+            },
+            Example {
+                example: r#"["1.2.3", "1.11.0-beta.1", "2.0.0-beta.1"] | semver max-satisfying --include-prerelease "^1""#,
+                description: "Find the highest version matching a requirement, including pre-releases",
+                result: Some(Value::test_string("1.11.0-beta.1")),
+            },
+            Example {
+                example: r#"["1.2.3", "1.4.0", "2.1.0"] | semver max-satisfying --dialect npm "~1.2 || ^1.4.0 <1.5""#,
+                description: "Find the highest version matching an npm range",
+                result: Some(Value::test_string("1.4.0")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let req = VersionReqValue::try_from_value(&call.req(0)?, get_dialect(call)?)?;
+        find_extremum(engine, call, input, Some(req), Ordering::Greater)
+    }
+}
+
+impl PluginCommand for SemverMinSatisfying {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver min-satisfying"
+    }
+
+    fn description(&self) -> &str {
+        "Return the lowest version of a list that satisfies a version requirement"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Pre-release versions only match if the requirement explicitly mentions a pre-release of the same version, unless `--include-prerelease` is set. Returns nothing if no version matches."
+    }
+
+    fn signature(&self) -> Signature {
+        signature(self.name(), true)
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![Example {
+            example: r#"["1.2.3", "1.10.0", "2.0.0"] | semver min-satisfying ">=1.5""#,
+            description: "Find the lowest version matching a requirement",
+            result: Some(Value::test_string("1.10.0")),
+        }]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let req = VersionReqValue::try_from_value(&call.req(0)?, get_dialect(call)?)?;
+        find_extremum(engine, call, input, Some(req), Ordering::Less)
+    }
+}
+
+fn signature(name: &str, with_req: bool) -> Signature {
+    let signature = Signature::build(name);
+    let signature = if with_req {
+        dialect_flag(signature).required(
+            "requirement",
+            SyntaxShape::Any,
+            "A valid version requirement, either as a string or a `semver-req` value",
+        )
+    } else {
+        signature
+    };
+    signature
+        .switch(
+            "include-prerelease",
+            "Consider pre-release versions as candidates",
+            Some('p'),
+        )
+        .named(
+            "by",
+            SyntaxShape::String,
+            "Look for the versions in the given column of a table",
+            Some('b'),
+        )
+        .input_output_types(vec![
+            (Type::List(Box::new(Type::String)), Type::String),
+            (Type::List(Box::new(custom_type())), custom_type()),
+            (Type::table(), Type::record()),
+        ])
+}
+
+/// Return the element of the input whose version is the greatest (or lowest, depending on
+/// `ordering`) amongst the ones that are candidates.
+fn find_extremum(
+    engine: &EngineInterface,
+    call: &EvaluatedCall,
+    input: PipelineData,
+    req: Option<VersionReqValue>,
+    ordering: Ordering,
+) -> Result<PipelineData, LabeledError> {
+    let config = SemverConfig::from_engine(engine)?;
+    let include_prerelease = call.has_flag("include-prerelease")?;
+    let by: Option<Spanned<String>> = call.get_flag("by")?;
+
+    let mut best: Option<(SemverCustomValue, Value)> = None;
+    for value in input {
+        let version = match &by {
+            Some(column) => {
+                SemverCustomValue::try_from_value(get_column(&value, column)?, config.lenient)?
+            }
+            None => SemverCustomValue::try_from_value(&value, config.lenient)?,
+        };
+        let is_candidate = match &req {
+            Some(req) => req.matches(&version, include_prerelease),
+            None => include_prerelease || version.pre.is_empty(),
+        };
+        if is_candidate
+            && best
+                .as_ref()
+                .is_none_or(|(b, _)| config.cmp_versions(&version, b) == ordering)
+        {
+            best = Some((version, value));
+        }
+    }
+
+    Ok(best
+        .map_or_else(|| Value::nothing(call.head), |(_, value)| value)
+        .into_pipeline_data())
+}
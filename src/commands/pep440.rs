@@ -0,0 +1,197 @@
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Example, LabeledError, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type, Value,
+};
+
+use crate::{
+    SemverPlugin,
+    custom_value::{Pep440CustomValue, SemverCustomValue},
+    pep440::Pep440Specifiers,
+};
+
+use super::{custom_type, pep440_custom_type};
+
+pub struct SemverPep440Match;
+pub struct SemverPep440ToSemver;
+
+impl PluginCommand for SemverPep440Match {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver pep440 match"
+    }
+
+    fn description(&self) -> &str {
+        "Match a PEP 440 version against version specifiers, e.g. `>=1.2, !=1.3.*`"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Pre-releases and dev-releases only match if one of the specifiers mentions a pre-release, unless `--include-prerelease` is set."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "specifiers",
+                SyntaxShape::String,
+                "Comma-separated version specifiers",
+            )
+            .switch(
+                "include-prerelease",
+                "Allow pre-releases and dev-releases to match",
+                Some('p'),
+            )
+            .filter()
+            .input_output_types(vec![
+                (Type::String, Type::Bool),
+                (pep440_custom_type(), Type::Bool),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::Bool)),
+                ),
+                (
+                    Type::List(Box::new(pep440_custom_type())),
+                    Type::List(Box::new(Type::Bool)),
+                ),
+            ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"["1.4.1", "1.4.5", "1.5.0"] | semver pep440 match "~=1.4.2""#,
+                description: "Match versions against a compatible release specifier",
+                result: Some(Value::test_list(vec![
+                    Value::test_bool(false),
+                    Value::test_bool(true),
+                    Value::test_bool(false),
+                ])),
+            },
+            Example {
+                example: r#""1.2.5" | semver pep440 match ">=1.0, !=1.2.*""#,
+                description: "Exclude a series of versions",
+                result: Some(Value::test_bool(false)),
+            },
+            Example {
+                example: r#""2.0rc1" | semver pep440 match --include-prerelease ">=1.0""#,
+                description: "Allow pre-releases to match",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                example: r#"["1.0", "v1.0"] | semver pep440 match "===1.0""#,
+                description: "Match versions as written with arbitrary equality",
+                result: Some(Value::test_list(vec![
+                    Value::test_bool(true),
+                    Value::test_bool(false),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let specifiers: Spanned<String> = call.req(0)?;
+        let specifiers = Pep440Specifiers::parse(&specifiers.item)
+            .map_err(|e| e.into_labeled_error(specifiers.span))?;
+        let include_prerelease = call.has_flag("include-prerelease")?;
+        let span = call.head;
+
+        Ok(input.map(
+            move |v| match Pep440CustomValue::try_from(&v) {
+                Ok(version) => {
+                    // pep440 values are normalized, so only strings are matched as written
+                    let raw = v
+                        .as_str()
+                        .map_or_else(|_| version.0.to_string(), str::to_owned);
+                    Value::bool(
+                        specifiers.matches(&version.0, &raw, include_prerelease),
+                        span,
+                    )
+                }
+                Err(e) => Value::error(e, span),
+            },
+            engine.signals(),
+        )?)
+    }
+}
+
+impl PluginCommand for SemverPep440ToSemver {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver pep440 to-semver"
+    }
+
+    fn description(&self) -> &str {
+        "Convert a PEP 440 version into the equivalent semver value"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Only versions without an epoch, post-release or dev-release, and with at most 3 release segments, have a SemVer equivalent. Pre-releases become `alpha.N`, `beta.N` or `rc.N`, and the local version label becomes the build metadata."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).input_output_types(vec![
+            (Type::String, custom_type()),
+            (pep440_custom_type(), custom_type()),
+            (
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(custom_type())),
+            ),
+            (
+                Type::List(Box::new(pep440_custom_type())),
+                Type::List(Box::new(custom_type())),
+            ),
+        ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""1.2rc3+ubuntu.1" | semver pep440 to-semver"#,
+                description: "Convert a PEP 440 pre-release into a semver value",
+                result: Some(SemverCustomValue::test_value("1.2.0-rc.3+ubuntu.1")),
+            },
+            Example {
+                example: r#""1.2.3" | into pep440 | semver pep440 to-semver | semver bump minor"#,
+                description: "Bump a Python version using SemVer semantics",
+                result: Some(SemverCustomValue::test_value("1.3.0")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let head = call.head;
+
+        Ok(input.map(
+            move |v| convert(&v).unwrap_or_else(|e| Value::error(e, head)),
+            engine.signals(),
+        )?)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn convert(input: &Value) -> Result<Value, ShellError> {
+    let span = input.span();
+    let version = Pep440CustomValue::try_from(input)?;
+    let semver = version
+        .0
+        .to_semver()
+        .map_err(|e| ShellError::IncorrectValue {
+            msg: e.to_string(),
+            val_span: span,
+            call_span: span,
+        })?;
+    Ok(SemverCustomValue(semver).into_value(span))
+}
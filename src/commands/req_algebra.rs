@@ -0,0 +1,271 @@
+use nu_plugin::{EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Example, LabeledError, ShellError, Signature, SyntaxShape, Type, Value};
+
+use crate::{
+    SemverPlugin,
+    custom_value::SemverReqCustomValue,
+    range::{self, VersionRange},
+    version::VersionReqValue,
+};
+
+use super::{dialect_flag, get_dialect, req_custom_type};
+
+pub struct SemverReqIntersect;
+pub struct SemverReqUnion;
+pub struct SemverReqSimplify;
+pub struct SemverReqIsEmpty;
+
+impl SimplePluginCommand for SemverReqIntersect {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver req intersect"
+    }
+
+    fn description(&self) -> &str {
+        "Compute the requirement matching the versions that satisfy all the given requirements"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The result is returned in a canonical form, made of at most a lower and an upper bound per `||` alternative. If the requirements have no common solution, the result is `<0.0.0-0`, which matches no version."
+    }
+
+    fn signature(&self) -> Signature {
+        signature(self.name(), req_custom_type())
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""^1.2, <1.5" | semver req intersect ">=1.4""#,
+                description: "Intersect two version requirements",
+                result: Some(SemverReqCustomValue::test_value(">=1.4.0, <1.5.0")),
+            },
+            Example {
+                example: r#"["^1", "~1.3", ">1.3.2"] | semver req intersect"#,
+                description: "Intersect a list of version requirements",
+                result: Some(SemverReqCustomValue::test_value(">1.3.2, <1.4.0")),
+            },
+            Example {
+                example: r#""1.x || 3.x" | semver req intersect --dialect npm ">=1.5.0 <3.2.0""#,
+                description: "Intersect npm ranges made of several alternatives",
+                result: Some(SemverReqCustomValue::test_value(
+                    ">=1.5.0, <2.0.0 || >=3.0.0, <3.2.0",
+                )),
+            },
+            Example {
+                example: r#""^1" | semver req intersect "^2" | semver req simplify"#,
+                description: "Disjoint requirements intersect into a requirement matching no version",
+                result: Some(SemverReqCustomValue::test_value("<0.0.0-0")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        _engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let ranges = intersection(call, input)?;
+        Ok(SemverReqCustomValue(range::to_reqs(&ranges)).into_value(call.head))
+    }
+}
+
+impl SimplePluginCommand for SemverReqUnion {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver req union"
+    }
+
+    fn description(&self) -> &str {
+        "Compute the requirement matching the versions that satisfy any of the given requirements"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The result is returned in a canonical form, with one `||` alternative per disjoint range of versions, sorted from the lowest to the highest versions."
+    }
+
+    fn signature(&self) -> Signature {
+        signature(self.name(), req_custom_type())
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""^1.2" | semver req union ">=1.5, <3""#,
+                description: "Merge two overlapping version requirements",
+                result: Some(SemverReqCustomValue::test_value(">=1.2.0, <3.0.0")),
+            },
+            Example {
+                example: r#""<1" | semver req union ">=2""#,
+                description: "Disjoint version requirements are kept as separate alternatives",
+                result: Some(SemverReqCustomValue::test_value("<1.0.0 || >=2.0.0")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        _engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let ranges = collect_reqs(call, input)?
+            .iter()
+            .flat_map(VersionReqValue::ranges)
+            .collect::<Vec<_>>();
+        let ranges = range::union(ranges);
+        Ok(SemverReqCustomValue(range::to_reqs(&ranges)).into_value(call.head))
+    }
+}
+
+impl SimplePluginCommand for SemverReqSimplify {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver req simplify"
+    }
+
+    fn description(&self) -> &str {
+        "Rewrite a version requirement into its canonical form"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The canonical form is made of at most a lower and an upper bound, with fully specified versions. Overlapping `||` alternatives are merged."
+    }
+
+    fn signature(&self) -> Signature {
+        dialect_flag(Signature::build(self.name())).input_output_types(vec![
+            (Type::String, req_custom_type()),
+            (req_custom_type(), req_custom_type()),
+        ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#"">=1.0.0, ^1.2.3, <1.9" | semver req simplify"#,
+                description: "Simplify a version requirement",
+                result: Some(SemverReqCustomValue::test_value(">=1.2.3, <1.9.0")),
+            },
+            Example {
+                example: r#""~0.3" | semver req simplify"#,
+                description: "Show the explicit bounds of a tilde requirement",
+                result: Some(SemverReqCustomValue::test_value(">=0.3.0, <0.4.0")),
+            },
+            Example {
+                example: r#""1.2 - 1.4 || ^1.3.0 <1.6 || 3" | semver req simplify --dialect npm"#,
+                description: "Merge the overlapping alternatives of an npm range",
+                result: Some(SemverReqCustomValue::test_value(
+                    ">=1.2.0, <1.6.0 || >=3.0.0, <4.0.0",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        _engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let req = VersionReqValue::try_from_value(input, get_dialect(call)?)?;
+        let ranges = range::union(req.ranges());
+        Ok(SemverReqCustomValue(range::to_reqs(&ranges)).into_value(call.head))
+    }
+}
+
+impl SimplePluginCommand for SemverReqIsEmpty {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver req is-empty"
+    }
+
+    fn description(&self) -> &str {
+        "Check whether no version can satisfy all the given requirements"
+    }
+
+    fn signature(&self) -> Signature {
+        signature(self.name(), Type::Bool)
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""^1.2, <1.5" | semver req is-empty ">=1.4""#,
+                description: "Check that two requirements have a common solution",
+                result: Some(Value::test_bool(false)),
+            },
+            Example {
+                example: r#"">1.2.3, <1.2.4" | semver req is-empty"#,
+                description: "Check whether a requirement can be satisfied at all",
+                result: Some(Value::test_bool(true)),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        _engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let ranges = intersection(call, input)?;
+        Ok(Value::bool(ranges.is_empty(), call.head))
+    }
+}
+
+fn signature(name: &str, output: Type) -> Signature {
+    dialect_flag(Signature::build(name))
+        .rest(
+            "requirements",
+            SyntaxShape::Any,
+            "Additional version requirements, either as strings or `semver-req` values",
+        )
+        .input_output_types(vec![
+            (Type::String, output.clone()),
+            (req_custom_type(), output.clone()),
+            (Type::List(Box::new(Type::Any)), output),
+        ])
+}
+
+/// Gather the requirements from the input (a single requirement or a list of requirements) and
+/// from the rest arguments.
+#[allow(clippy::result_large_err)]
+fn collect_reqs(call: &EvaluatedCall, input: &Value) -> Result<Vec<VersionReqValue>, ShellError> {
+    let dialect = get_dialect(call)?;
+    let parse = |value: &Value| VersionReqValue::try_from_value(value, dialect);
+    let mut reqs = match input {
+        Value::List { vals, .. } => vals.iter().map(parse).collect::<Result<Vec<_>, _>>()?,
+        Value::Nothing { .. } => vec![],
+        value => vec![parse(value)?],
+    };
+    for value in call.rest::<Value>(0)? {
+        reqs.push(parse(&value)?);
+    }
+    Ok(reqs)
+}
+
+/// The disjoint ranges of versions satisfying all the given requirements. A version satisfies a
+/// requirement if it falls in any of its ranges, so the ranges of each requirement are
+/// intersected pairwise with the ranges accumulated so far.
+#[allow(clippy::result_large_err)]
+fn intersection(call: &EvaluatedCall, input: &Value) -> Result<Vec<VersionRange>, ShellError> {
+    Ok(collect_reqs(call, input)?
+        .iter()
+        .fold(vec![VersionRange::FULL], |acc, req| {
+            let ranges = req.ranges();
+            range::union(
+                acc.iter()
+                    .flat_map(|a| ranges.iter().map(move |b| a.intersect(b)))
+                    .collect::<Vec<_>>(),
+            )
+        }))
+}
@@ -0,0 +1,223 @@
+use nu_plugin::{EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Example, LabeledError, Record, Signature, Span, Type, Value, record};
+use semver::{Comparator, Op, VersionReq};
+
+use crate::{
+    SemverPlugin,
+    range::{self, VersionRange},
+    version::VersionReqValue,
+};
+
+use super::{dialect_flag, get_dialect, req_custom_type};
+
+pub struct SemverReqExplain;
+
+impl SimplePluginCommand for SemverReqExplain {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver req explain"
+    }
+
+    fn description(&self) -> &str {
+        "Expand a version requirement into explicit ranges of versions"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Each comparator of the requirement is expanded into the range of versions it matches. If the requirement has several comparators, a row shows the range matched by all of them together. Requirements with several `||` alternatives are explained one alternative at a time, and a last row shows the ranges matched by the whole requirement."
+    }
+
+    fn signature(&self) -> Signature {
+        dialect_flag(Signature::build(self.name())).input_output_types(vec![
+            (Type::String, explain_type()),
+            (req_custom_type(), explain_type()),
+        ])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: r#""^0.2.3" | semver req explain"#,
+                description: "Explain a caret requirement on a 0.x version",
+                result: Some(Value::test_list(vec![Value::test_record(record!(
+                    "comparator" => Value::test_string("^0.2.3"),
+                    "range" => Value::test_string(">=0.2.3, <0.3.0"),
+                    "note" => Value::test_string("Allows patch updates only, since the minor version acts as the major version for 0.x versions"),
+                    "prerelease" => Value::test_string("Pre-release versions are not matched"),
+                ))])),
+            },
+            Example {
+                example: r#"(">=1.2.0-beta.1, <2" | semver req explain).range"#,
+                description: "Show the ranges matched by each comparator, and by the whole requirement",
+                result: Some(Value::test_list(vec![
+                    Value::test_string(">=1.2.0-beta.1"),
+                    Value::test_string("<2.0.0"),
+                    Value::test_string(">=1.2.0-beta.1, <2.0.0"),
+                ])),
+            },
+            Example {
+                example: r#"("1.x || 3.1 - 3.4" | semver req explain --dialect npm).range"#,
+                description: "Explain an npm range made of several alternatives",
+                result: Some(Value::test_list(vec![
+                    Value::test_string(">=1.0.0, <2.0.0"),
+                    Value::test_string(">=3.1.0"),
+                    Value::test_string("<3.5.0"),
+                    Value::test_string(">=3.1.0, <3.5.0"),
+                    Value::test_string(">=1.0.0, <2.0.0 || >=3.1.0, <3.5.0"),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        _engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let req = VersionReqValue::try_from_value(input, get_dialect(call)?)?;
+
+        let mut rows: Vec<Value> = req
+            .alternatives()
+            .iter()
+            .flat_map(|alternative| explain_alternative(alternative, span))
+            .collect();
+
+        if req.alternatives().len() > 1 {
+            let ranges: Vec<String> = range::union(req.ranges())
+                .into_iter()
+                .map(range_string)
+                .collect();
+            let alternatives: Vec<String> = req
+                .alternatives()
+                .iter()
+                .map(|alt| alt.to_string())
+                .collect();
+            rows.push(explain_row(
+                alternatives.join(" || "),
+                ranges.join(" || "),
+                "Versions need to satisfy any of the alternatives".to_string(),
+                "Pre-release versions are matched according to each alternative".to_string(),
+                span,
+            ));
+        }
+
+        Ok(Value::list(rows, span))
+    }
+}
+
+/// Explain each comparator of a requirement, followed by the requirement as a whole if it doesn't
+/// have exactly one comparator.
+fn explain_alternative(req: &VersionReq, span: Span) -> Vec<Value> {
+    let mut rows: Vec<Value> = req
+        .comparators
+        .iter()
+        .map(|cmp| {
+            explain_row(
+                cmp.to_string(),
+                range_string(VersionRange::from_comparator(cmp)),
+                op_note(cmp),
+                prerelease_note(cmp),
+                span,
+            )
+        })
+        .collect();
+
+    if req.comparators.len() != 1 {
+        let note = if req.comparators.is_empty() {
+            "Matches any version".to_string()
+        } else {
+            "Versions need to satisfy all the comparators".to_string()
+        };
+        let prerelease = if req.comparators.iter().any(|c| !c.pre.is_empty()) {
+            "Pre-release versions only match if one of the comparators has a pre-release of the same version"
+        } else {
+            "Pre-release versions are not matched"
+        };
+        rows.push(explain_row(
+            req.to_string(),
+            range_string(VersionRange::from_req(req)),
+            note,
+            prerelease.to_string(),
+            span,
+        ));
+    }
+
+    rows
+}
+
+fn range_string(range: VersionRange) -> String {
+    range.to_req().to_string()
+}
+
+fn explain_row(
+    comparator: String,
+    range: String,
+    note: String,
+    prerelease: String,
+    span: Span,
+) -> Value {
+    let mut record = Record::new();
+    record.push("comparator", Value::string(comparator, span));
+    record.push("range", Value::string(range, span));
+    record.push("note", Value::string(note, span));
+    record.push("prerelease", Value::string(prerelease, span));
+    Value::record(record, span)
+}
+
+/// Describe the semantics of the comparator's operator, in plain words.
+fn op_note(cmp: &Comparator) -> String {
+    let partial = match (cmp.minor, cmp.patch) {
+        (None, _) => format!("{}.x.x", cmp.major),
+        (Some(minor), None) => format!("{}.{minor}.x", cmp.major),
+        (Some(minor), Some(patch)) => format!("{}.{minor}.{patch}", cmp.major),
+    };
+    match cmp.op {
+        Op::Exact | Op::Wildcard if cmp.patch.is_none() => format!("Matches any {partial} version"),
+        Op::Exact => format!("Matches exactly {partial}"),
+        Op::Greater => format!("Matches versions above {partial}"),
+        Op::GreaterEq => format!("Matches {partial} and above"),
+        Op::Less => format!("Matches versions below {partial}"),
+        Op::LessEq => format!("Matches {partial} and below"),
+        Op::Tilde if cmp.minor.is_none() => "Allows minor and patch updates".to_string(),
+        Op::Tilde => "Allows patch updates only".to_string(),
+        Op::Caret => match (cmp.major, cmp.minor, cmp.patch) {
+            (0, Some(0), Some(_)) => {
+                "Matches exactly this version, since every change of a 0.0.x version is considered breaking".to_string()
+            }
+            (0, Some(_), _) => {
+                "Allows patch updates only, since the minor version acts as the major version for 0.x versions".to_string()
+            }
+            _ => "Allows minor and patch updates".to_string(),
+        },
+        _ => "Unknown operator".to_string(),
+    }
+}
+
+/// Describe how pre-release versions are handled by a comparator.
+fn prerelease_note(cmp: &Comparator) -> String {
+    if cmp.pre.is_empty() {
+        "Pre-release versions are not matched".to_string()
+    } else {
+        format!(
+            "Pre-releases of {}.{}.{} are matched, pre-releases of other versions are not",
+            cmp.major,
+            cmp.minor.unwrap_or(0),
+            cmp.patch.unwrap_or(0)
+        )
+    }
+}
+
+fn explain_type() -> Type {
+    Type::Table(
+        vec![
+            ("comparator".to_string(), Type::String),
+            ("range".to_string(), Type::String),
+            ("note".to_string(), Type::String),
+            ("prerelease".to_string(), Type::String),
+        ]
+        .into(),
+    )
+}
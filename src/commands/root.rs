@@ -1,4 +1,4 @@
-use crate::SemverPlugin;
+use crate::{SemverPlugin, config::SemverConfig};
 use nu_plugin::{EvaluatedCall, SimplePluginCommand};
 use nu_protocol::{LabeledError, Signature, Value};
 
@@ -15,6 +15,10 @@ impl SimplePluginCommand for SemverCommand {
         "Show all the semver commands"
     }
 
+    fn extra_description(&self) -> &str {
+        "The effective plugin configuration, read from `$env.config.plugins.semver`, is shown after the list of commands."
+    }
+
     fn signature(&self) -> Signature {
         Signature::build(self.name())
     }
@@ -26,6 +30,10 @@ impl SimplePluginCommand for SemverCommand {
         call: &EvaluatedCall,
         _input: &Value,
     ) -> Result<Value, LabeledError> {
-        Ok(Value::string(engine.get_help()?, call.head))
+        let config = SemverConfig::from_engine(engine)?;
+        Ok(Value::string(
+            format!("{}\n{}", engine.get_help()?, config.describe()),
+            call.head,
+        ))
     }
 }
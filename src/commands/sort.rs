@@ -1,11 +1,23 @@
-use std::ops::Deref;
+use crate::{
+    SemverPlugin,
+    config::SemverConfig,
+    custom_value::SemverCustomValue,
+    scheme::{
+        DebScheme, GoScheme, MavenScheme, Pep440Scheme, RpmScheme, Scheme, SemverScheme,
+        VersioningScheme,
+    },
+};
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Example, IntoInterruptiblePipelineData, LabeledError, PipelineData, ShellError, Signature,
+    Spanned, SyntaxShape, Type, Value, record,
+};
+
+use super::{custom_type, get_column, get_scheme, scheme_flag};
 
-use crate::{SemverPlugin, version::VersionValue};
-use nu_plugin::{EvaluatedCall, SimplePluginCommand};
-use nu_protocol::{Example, LabeledError, Signature, Type, Value};
 pub struct SemverSort;
 
-impl SimplePluginCommand for SemverSort {
+impl PluginCommand for SemverSort {
     type Plugin = SemverPlugin;
 
     fn name(&self) -> &str {
@@ -13,62 +25,183 @@ impl SimplePluginCommand for SemverSort {
     }
 
     fn description(&self) -> &str {
-        "Sort a list of versions using SemVer ordering."
+        "Sort a list of versions using SemVer ordering, or the ordering of another versioning scheme."
     }
 
     fn extra_description(&self) -> &str {
-        "Note: every item in the list needs to be a well-formed SemVer version."
+        "Note: every item in the list (or in the column given with `--by`) needs to be a well-formed version of the selected scheme. For SemVer, versions can be strings or semver values. The `deb` and `rpm` schemes follow the algorithms of `dpkg --compare-versions` and `rpmvercmp`, including the special meaning of `~` and `^`. The `go` scheme follows `golang.org/x/mod/semver`, where invalid versions sort first. The `maven` scheme follows Maven's `ComparableVersion`, where e.g. `1.0-SNAPSHOT` sorts before `1.0`, and `1.0.Final` equals `1.0`."
     }
 
     fn signature(&self) -> Signature {
-        Signature::build(self.name())
+        scheme_flag(Signature::build(self.name()))
             .switch(
                 "reverse",
                 "Sort the versions in descending order",
                 Some('r'),
             )
-            .input_output_type(
-                Type::List(Box::new(Type::String)),
-                Type::List(Box::new(Type::String)),
+            .switch(
+                "lenient",
+                "Coerce versions that aren't valid SemVer (e.g. `v1.2`) instead of failing. The original strings are returned. Only applies to the semver scheme.",
+                Some('l'),
+            )
+            .named(
+                "by",
+                SyntaxShape::String,
+                "Sort a table using the versions in the given column",
+                Some('b'),
             )
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::String)),
+                ),
+                (
+                    Type::List(Box::new(custom_type())),
+                    Type::List(Box::new(custom_type())),
+                ),
+                (Type::table(), Type::table()),
+            ])
     }
 
     fn examples(&'_ self) -> Vec<Example<'_>> {
-        vec![Example {
-            example: r#"["3.2.1", "2.3.4", "3.2.2", "2.3.4-beta.1", "2.3.4-alpha.1", "2.3.4-alpha.2"] | semver sort"#,
-            description: "sort versions by SemVer semantics.",
-            result: Some(Value::test_list(vec![
-                Value::test_string("2.3.4-alpha.1"),
-                Value::test_string("2.3.4-alpha.2"),
-                Value::test_string("2.3.4-beta.1"),
-                Value::test_string("2.3.4"),
-                Value::test_string("3.2.1"),
-                Value::test_string("3.2.2"),
-            ])),
-        }]
+        vec![
+            Example {
+                example: r#"["3.2.1", "2.3.4", "3.2.2", "2.3.4-beta.1", "2.3.4-alpha.1", "2.3.4-alpha.2"] | semver sort"#,
+                description: "sort versions by SemVer semantics.",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2.3.4-alpha.1"),
+                    Value::test_string("2.3.4-alpha.2"),
+                    Value::test_string("2.3.4-beta.1"),
+                    Value::test_string("2.3.4"),
+                    Value::test_string("3.2.1"),
+                    Value::test_string("3.2.2"),
+                ])),
+            },
+            Example {
+                example: r#"["v1.10", "v1.2.3", "release-1.9"] | semver sort --lenient"#,
+                description: "sort loosely formatted versions by SemVer semantics.",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("v1.2.3"),
+                    Value::test_string("release-1.9"),
+                    Value::test_string("v1.10"),
+                ])),
+            },
+            Example {
+                example: r#"["1.10.0", "1.2.0"] | into semver | semver sort"#,
+                description: "sort semver values, keeping them as semver values.",
+                result: Some(Value::test_list(vec![
+                    SemverCustomValue::test_value("1.2.0"),
+                    SemverCustomValue::test_value("1.10.0"),
+                ])),
+            },
+            Example {
+                example: r#"[[name version]; [foo "1.10.0"] [bar "1.2.0"]] | semver sort --by version"#,
+                description: "sort the rows of a table by the versions in one of its columns.",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record!(
+                        "name" => Value::test_string("bar"),
+                        "version" => Value::test_string("1.2.0"),
+                    )),
+                    Value::test_record(record!(
+                        "name" => Value::test_string("foo"),
+                        "version" => Value::test_string("1.10.0"),
+                    )),
+                ])),
+            },
+            Example {
+                example: r#"["1:2.3.4-0ubuntu1~bpo", "2.3.4-1", "2.3.4~rc1-1", "2.3.4-0ubuntu1"] | semver sort --scheme deb"#,
+                description: "sort Debian package versions.",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2.3.4~rc1-1"),
+                    Value::test_string("2.3.4-0ubuntu1"),
+                    Value::test_string("2.3.4-1"),
+                    Value::test_string("1:2.3.4-0ubuntu1~bpo"),
+                ])),
+            },
+            Example {
+                example: r#"["2.3.4-10.el9", "2.3.4-2.el9", "2.3.4~rc1-1.el9", "2.3.4^git1-1.el9"] | semver sort --scheme rpm"#,
+                description: "sort RPM package versions.",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2.3.4~rc1-1.el9"),
+                    Value::test_string("2.3.4-2.el9"),
+                    Value::test_string("2.3.4-10.el9"),
+                    Value::test_string("2.3.4^git1-1.el9"),
+                ])),
+            },
+            Example {
+                example: r#"["v1.10.0", "v1.2", "v1.2.0-pre", "v0.0.0-20191109021931-daa7c04131f5"] | semver sort --scheme go"#,
+                description: "sort Go module versions, including shorthands and pseudo-versions.",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("v0.0.0-20191109021931-daa7c04131f5"),
+                    Value::test_string("v1.2.0-pre"),
+                    Value::test_string("v1.2"),
+                    Value::test_string("v1.10.0"),
+                ])),
+            },
+            Example {
+                example: r#"["1.0-sp1", "1.0.RELEASE", "1.0-SNAPSHOT", "1.0-alpha-1", "1.0-beta"] | semver sort --scheme maven"#,
+                description: "sort Maven versions, where qualifiers have a special meaning.",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("1.0-alpha-1"),
+                    Value::test_string("1.0-beta"),
+                    Value::test_string("1.0-SNAPSHOT"),
+                    Value::test_string("1.0.RELEASE"),
+                    Value::test_string("1.0-sp1"),
+                ])),
+            },
+        ]
     }
 
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &nu_plugin::EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
-        input: &Value,
-    ) -> Result<Value, LabeledError> {
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
         let reverse = call.has_flag("reverse")?;
+        let config = SemverConfig::from_engine(engine)?;
+        let lenient = call.has_flag("lenient")? || config.lenient;
+        let by: Option<Spanned<String>> = call.get_flag("by")?;
         let span = call.head;
-        let values = input.as_list()?;
-        let mut versions = values
-            .iter()
-            .map(VersionValue::try_from)
-            .collect::<Result<Vec<_>, _>>()?;
-        versions.sort_by(|a, b| a.deref().cmp(b.deref()));
+        let values: Vec<Value> = input.into_iter().collect();
+        let by = by.as_ref();
+        let semver = SemverScheme {
+            config: &config,
+            lenient,
+        };
+        let mut versions = match get_scheme(call)? {
+            Scheme::Semver => sort(&semver, values, by)?,
+            Scheme::Pep440 => sort(&Pep440Scheme, values, by)?,
+            Scheme::Deb => sort(&DebScheme, values, by)?,
+            Scheme::Rpm => sort(&RpmScheme, values, by)?,
+            Scheme::Go => sort(&GoScheme, values, by)?,
+            Scheme::Maven => sort(&MavenScheme, values, by)?,
+        };
         if reverse {
             versions.reverse();
         }
-        Ok(Value::list(
-            versions.into_iter().map(|v| v.into_value()).collect(),
-            span,
-        ))
+        Ok(versions.into_pipeline_data(span, engine.signals().clone()))
     }
 }
+
+/// Sort the values by their version (or the version in the `by` column), using the given scheme.
+#[allow(clippy::result_large_err)]
+fn sort<S: VersioningScheme>(
+    scheme: &S,
+    values: Vec<Value>,
+    by: Option<&Spanned<String>>,
+) -> Result<Vec<Value>, ShellError> {
+    let mut versions = values
+        .into_iter()
+        .map(|v| {
+            let version = match by {
+                Some(column) => scheme.parse(get_column(&v, column)?)?,
+                None => scheme.parse(&v)?,
+            };
+            Ok((version, v))
+        })
+        .collect::<Result<Vec<_>, ShellError>>()?;
+    versions.sort_by(|(a, _), (b, _)| scheme.compare(a, b));
+    Ok(versions.into_iter().map(|(_, v)| v).collect())
+}
@@ -1,7 +1,11 @@
 use nu_plugin::{EvaluatedCall, SimplePluginCommand};
-use nu_protocol::{Example, IntoValue, LabeledError, Record, Signature, Type, Value, record};
+use nu_protocol::{Example, LabeledError, Signature, Type, Value, record};
 
-use crate::{SemverPlugin, custom_value::SemverCustomValue};
+use crate::{
+    SemverPlugin,
+    config::SemverConfig,
+    custom_value::{SemverCustomValue, version_record},
+};
 
 use super::{custom_type, record_type};
 
@@ -55,20 +59,14 @@ impl SimplePluginCommand for SemverToRecord {
     fn run(
         &self,
         _plugin: &Self::Plugin,
-        _engine: &nu_plugin::EngineInterface,
+        engine: &nu_plugin::EngineInterface,
         call: &EvaluatedCall,
         input: &Value,
     ) -> Result<Value, LabeledError> {
+        let config = SemverConfig::from_engine(engine)?;
         let span = call.head;
-        let version: SemverCustomValue = input.try_into()?;
+        let version = SemverCustomValue::try_from_value(input, config.lenient)?;
 
-        let mut record = Record::new();
-        record.push("major", Value::int(version.major as i64, span));
-        record.push("minor", Value::int(version.minor as i64, span));
-        record.push("patch", Value::int(version.patch as i64, span));
-        record.push("pre", Value::string(version.pre.as_str(), span));
-        record.push("build", Value::string(version.build.as_str(), span));
-
-        Ok(record.into_value(span))
+        Ok(version_record(&version, span))
     }
 }
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Example, LabeledError, Signature, Spanned, SyntaxShape, Type, Value, record};
+
+use crate::{
+    SemverPlugin, config::SemverConfig, custom_value::SemverCustomValue, workspace::Workspace,
+};
+
+use super::get_level;
+
+pub struct SemverWorkspaceBump;
+
+impl SimplePluginCommand for SemverWorkspaceBump {
+    type Plugin = SemverPlugin;
+
+    fn name(&self) -> &str {
+        "semver workspace bump"
+    }
+
+    fn description(&self) -> &str {
+        "Bump the version of a crate of a Cargo workspace, and the requirements of the crates depending on it"
+    }
+
+    fn extra_description(&self) -> &str {
+        "The requirements of `path` dependencies on the bumped crate are only rewritten when they don't match the new version anymore. If the crate inherits its version from `[workspace.package]`, the workspace version is bumped instead, which bumps every crate inheriting it. Returns a table of the edits, which are only planned with `--dry-run`."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required("crate", SyntaxShape::String, "The name of the crate to bump")
+            .required(
+                "level",
+                SyntaxShape::String,
+                "The version level to bump, as for `semver bump`",
+            )
+            .named(
+                "path",
+                SyntaxShape::Directory,
+                "A directory of the workspace (defaults to the current directory)",
+                Some('p'),
+            )
+            .named(
+                "label",
+                SyntaxShape::String,
+                "The pre-release label to use with the `pre`, `premajor`, `preminor`, `prepatch` and `prerelease` levels",
+                Some('l'),
+            )
+            .switch(
+                "dry-run",
+                "Only list the edits, without writing the manifests",
+                Some('n'),
+            )
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table(
+                    vec![
+                        ("path".to_string(), Type::String),
+                        ("crate".to_string(), Type::String),
+                        ("field".to_string(), Type::String),
+                        ("from".to_string(), Type::String),
+                        ("to".to_string(), Type::String),
+                    ]
+                    .into(),
+                ),
+            )])
+    }
+
+    fn examples(&'_ self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "semver workspace bump my-core minor --dry-run",
+                description: "List the edits needed to bump the minor version of a crate",
+                result: None,
+            },
+            Example {
+                example: "semver workspace bump my-core major",
+                description: "Bump the major version of a crate, and the requirements of its dependents",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &Self::Plugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let config = SemverConfig::from_engine(engine)?;
+        let package: Spanned<String> = call.req(0)?;
+        let (level, label) = get_level(call, 1)?;
+        let dir = PathBuf::from(engine.get_current_dir()?)
+            .join(call.get_flag::<String>("path")?.unwrap_or_default());
+
+        let mut workspace = Workspace::discover(&dir).map_err(|e| e.into_labeled_error(span))?;
+        let edits = workspace
+            .bump(&package.item, |current| {
+                let mut version = SemverCustomValue(current.clone());
+                version.bump(level, label.as_deref(), &config.prerelease_labels, None)?;
+                Ok(version.0)
+            })
+            .map_err(|e| e.into_labeled_error(package.span))?;
+        if !call.has_flag("dry-run")? {
+            workspace.save().map_err(|e| e.into_labeled_error(span))?;
+        }
+
+        let rows = edits
+            .into_iter()
+            .map(|edit| {
+                Value::record(
+                    record!(
+                        "path" => Value::string(edit.path.display().to_string(), span),
+                        "crate" => edit.package.map_or(Value::nothing(span), |p| Value::string(p, span)),
+                        "field" => Value::string(edit.field, span),
+                        "from" => Value::string(edit.from, span),
+                        "to" => Value::string(edit.to, span),
+                    ),
+                    span,
+                )
+            })
+            .collect();
+        Ok(Value::list(rows, span))
+    }
+}
@@ -0,0 +1,149 @@
+use std::{cmp::Ordering, fmt::Write};
+
+use nu_plugin::EngineInterface;
+use nu_protocol::{FromValue, LabeledError, ShellError, Span, Value};
+
+use crate::version::PrereleaseLabels;
+
+const SETTINGS: &[&str] = &[
+    "prerelease_labels",
+    "lenient",
+    "base_value",
+    "compare_build_metadata",
+];
+
+/// The error of engine calls made outside of a command invocation.
+const NO_EXECUTION_CONTEXT: &str = "A plugin execution context is required for this engine call";
+
+/// How semver values are converted into plain nushell values, e.g. by `to json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum BaseValue {
+    #[default]
+    String,
+    Record,
+}
+
+/// The plugin configuration, read from `$env.config.plugins.semver`.
+#[derive(Debug, Clone)]
+pub struct SemverConfig {
+    /// The chain of pre-release labels used by `semver bump`.
+    pub prerelease_labels: PrereleaseLabels,
+    /// Whether versions are coerced by default, as if `--lenient` was always passed.
+    pub lenient: bool,
+    pub base_value: BaseValue,
+    /// Whether build metadata is taken into account when ordering versions.
+    pub compare_build_metadata: bool,
+}
+
+impl Default for SemverConfig {
+    fn default() -> Self {
+        Self {
+            prerelease_labels: PrereleaseLabels::default(),
+            lenient: false,
+            base_value: BaseValue::default(),
+            compare_build_metadata: true,
+        }
+    }
+}
+
+impl SemverConfig {
+    pub fn from_engine(engine: &EngineInterface) -> Result<Self, LabeledError> {
+        match engine.get_plugin_config()? {
+            Some(config) => Self::from_value(&config),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Like [`SemverConfig::from_engine`], but falls back to the default configuration when the
+    /// engine has none to give, i.e. when a custom value is handled outside of a command
+    /// invocation. Other errors are returned.
+    pub fn from_engine_or_default(engine: &EngineInterface) -> Result<Self, LabeledError> {
+        match engine.get_plugin_config() {
+            Ok(Some(config)) => Self::from_value(&config),
+            Ok(None) => Ok(Self::default()),
+            // Custom value operations aren't run with an execution context, which the engine
+            // only reports with a generic error.
+            Err(ShellError::Generic(e)) if e.error == NO_EXECUTION_CONTEXT => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn from_value(value: &Value) -> Result<Self, LabeledError> {
+        let mut config = Self::default();
+        let record = value
+            .as_record()
+            .map_err(|_| invalid_config("The configuration should be a record", value.span()))?;
+
+        for (key, value) in record.iter() {
+            let span = value.span();
+            match key.as_str() {
+                "prerelease_labels" => {
+                    let labels = Vec::<String>::from_value(value.clone()).map_err(|_| {
+                        invalid_config("prerelease_labels should be a list of strings", span)
+                    })?;
+                    config.prerelease_labels = PrereleaseLabels::new(labels)
+                        .map_err(|e| invalid_config(e.to_string(), span))?;
+                }
+                "lenient" => {
+                    config.lenient = value
+                        .as_bool()
+                        .map_err(|_| invalid_config("lenient should be a boolean", span))?;
+                }
+                "base_value" => {
+                    config.base_value = value
+                        .as_str()
+                        .ok()
+                        .and_then(|s| s.parse().ok())
+                        .ok_or_else(|| {
+                            invalid_config("base_value should be either 'string' or 'record'", span)
+                        })?;
+                }
+                "compare_build_metadata" => {
+                    config.compare_build_metadata = value.as_bool().map_err(|_| {
+                        invalid_config("compare_build_metadata should be a boolean", span)
+                    })?;
+                }
+                _ => {
+                    return Err(invalid_config(
+                        format!(
+                            "Unknown setting '{key}'. Valid settings are: {}",
+                            SETTINGS.join(", ")
+                        ),
+                        span,
+                    ));
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Compare two versions, ignoring build metadata unless `compare_build_metadata` is set.
+    pub fn cmp_versions(&self, a: &semver::Version, b: &semver::Version) -> Ordering {
+        if self.compare_build_metadata {
+            a.cmp(b)
+        } else {
+            a.cmp_precedence(b)
+        }
+    }
+
+    /// A human-readable summary of the configuration, shown by the `semver` command.
+    pub fn describe(&self) -> String {
+        let mut out = String::from("Effective configuration ($env.config.plugins.semver):\n");
+        let labels: Vec<&str> = self.prerelease_labels.iter().collect();
+        let _ = writeln!(out, "  prerelease_labels: [{}]", labels.join(", "));
+        let _ = writeln!(out, "  lenient: {}", self.lenient);
+        let _ = writeln!(out, "  base_value: {}", self.base_value);
+        let _ = writeln!(
+            out,
+            "  compare_build_metadata: {}",
+            self.compare_build_metadata
+        );
+        out
+    }
+}
+
+fn invalid_config(msg: impl Into<String>, span: Span) -> LabeledError {
+    LabeledError::new("Invalid semver plugin configuration").with_label(msg, span)
+}
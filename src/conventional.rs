@@ -0,0 +1,88 @@
+use crate::version::Level;
+
+/// A commit message following the Conventional Commits specification, e.g.
+/// `feat(parser)!: support ranges`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalCommit {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+impl ConventionalCommit {
+    /// Parse a commit message, returning `None` if its first line isn't a conventional header.
+    pub fn parse(message: &str) -> Option<Self> {
+        let message = message.trim();
+        let (header, body) = message.split_once('\n').unwrap_or((message, ""));
+        let (prefix, description) = header.split_once(':')?;
+        let description = description.trim();
+
+        let (prefix, bang) = match prefix.strip_suffix('!') {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
+        let (kind, scope) = match prefix.split_once('(') {
+            Some((kind, scope)) => (kind, Some(scope.strip_suffix(')')?)),
+            None => (prefix, None),
+        };
+        let is_word = |s: &str| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        };
+        if !is_word(kind) || scope.is_some_and(|s| s.trim().is_empty()) || description.is_empty() {
+            return None;
+        }
+
+        let breaking = bang
+            || body.lines().any(|line| {
+                line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+            });
+        Some(Self {
+            kind: kind.to_lowercase(),
+            scope: scope.map(|s| s.trim().to_owned()),
+            breaking,
+            description: description.to_owned(),
+        })
+    }
+
+    /// The level this commit requires bumping: major for breaking changes, minor for features
+    /// and patch for fixes.
+    pub fn level(&self) -> Option<Level> {
+        if self.breaking {
+            Some(Level::Major)
+        } else {
+            match self.kind.as_str() {
+                "feat" => Some(Level::Minor),
+                "fix" => Some(Level::Patch),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// The highest level required by a set of commits, if any.
+pub fn summary_level<'a>(
+    commits: impl IntoIterator<Item = &'a ConventionalCommit>,
+) -> Option<Level> {
+    let rank = |level: &Level| match level {
+        Level::Major => 3,
+        Level::Minor => 2,
+        _ => 1,
+    };
+    commits
+        .into_iter()
+        .filter_map(ConventionalCommit::level)
+        .max_by_key(rank)
+}
+
+/// Apply the 0.x rule: while the major version is 0, breaking changes only bump the minor
+/// version.
+pub fn adjust_for_initial_development(level: Level, version: &semver::Version) -> Level {
+    if version.major == 0 && level == Level::Major {
+        Level::Minor
+    } else {
+        level
+    }
+}
@@ -1,10 +1,21 @@
 use std::{any::Any, cmp::Ordering, ops::Deref};
 
-use nu_protocol::{CustomValue, ShellError, Span, Value, casing::Casing};
-use semver::{BuildMetadata, Prerelease};
+use nu_protocol::{
+    CustomValue, Record, ShellError, Span, Type, Value,
+    ast::{Comparison, Operator},
+    casing::Casing,
+};
+use semver::{BuildMetadata, Op, Prerelease};
 use serde::{Deserialize, Serialize};
 
-use crate::version::{ALPHA, BETA, Level, RC, VersionError};
+use crate::{
+    calver::{CalverFormat, CalverVersion},
+    pep440::Pep440Version,
+    version::{
+        ALPHA, BETA, Dialect, Level, PrereleaseLabels, RC, VersionError, coerce,
+        not_coercible_error,
+    },
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct SemverCustomValue(pub semver::Version);
@@ -17,77 +28,46 @@ impl SemverCustomValue {
     pub fn bump(
         &mut self,
         level: Level,
+        pre_label: Option<&str>,
+        labels: &PrereleaseLabels,
         build_metadata: Option<String>,
     ) -> Result<(), VersionError> {
         match level {
             Level::Major => self.bump_major(),
             Level::Minor => self.bump_minor(),
             Level::Patch => self.bump_patch(),
-            Level::Alpha => {
-                let new_num = if let Some((current_level, num)) = self.pre_release_version_num()? {
-                    if current_level == ALPHA {
-                        // 1.2.3-alpha => 1.2.3-alpha.1
-                        // 1.2.3-alpha.1 => 1.2.3-alpha.2
-                        num.unwrap_or(0) + 1
-                    } else {
-                        // Unknown level or trying to downgrade from beta or rc
-                        return Err(VersionError::InvalidLevelForPrerelease(
-                            level,
-                            self.0.pre.to_string(),
-                        ));
-                    }
-                } else {
+            Level::Alpha => self.bump_pre(ALPHA, labels)?,
+            Level::Beta => self.bump_pre(BETA, labels)?,
+            Level::Rc => self.bump_pre(RC, labels)?,
+            Level::Pre => {
+                let label = match pre_label {
+                    Some(label) => label.to_owned(),
+                    // 1.2.3-beta.1 => 1.2.3-beta.2
                     // 1.2.3 => 1.2.4-alpha.1
-                    self.bump_patch();
-                    1
+                    None => match self.pre_release_version_num()? {
+                        Some((current_label, _)) => current_label,
+                        None => labels.first().to_owned(),
+                    },
                 };
-                self.0.pre = Prerelease::new(&format!("{ALPHA}.{new_num}"))?;
+                self.bump_pre(&label, labels)?;
             }
-            Level::Beta => {
-                let new_num = if let Some((current_level, num)) = self.pre_release_version_num()? {
-                    if current_level == ALPHA {
-                        // 1.2.3-alpha.2 => 1.2.3-beta.1
-                        1
-                    } else if current_level == BETA {
-                        // 1.2.3-beta => 1.2.3-beta.1
-                        // 1.2.3-beta.2 => 1.2.3-beta.3
-                        num.unwrap_or(0) + 1
-                    } else {
-                        // unknown level or trying to downgrade from rc
-                        return Err(VersionError::InvalidLevelForPrerelease(
-                            level,
-                            self.0.pre.to_string(),
-                        ));
-                    }
-                } else {
-                    // 1.2.3 => 1.2.4-beta.1
-                    self.bump_patch();
-                    1
-                };
-                self.0.pre = Prerelease::new(&format!("{BETA}.{new_num}"))?;
+            Level::Premajor => {
+                // 1.2.3 => 2.0.0-alpha.1
+                self.bump_major();
+                self.start_pre(pre_label.unwrap_or(labels.first()))?;
             }
-            Level::Rc => {
-                let new_num = if let Some((current_level, num)) = self.pre_release_version_num()? {
-                    if current_level == ALPHA || current_level == BETA {
-                        // 1.2.3-alpha.2 => 1.2.3-rc.1
-                        // 1.2.3-beta.2 => 1.2.3-rc.1
-                        1
-                    } else if current_level == RC {
-                        // 1.2.3 => 1.2.3-rc.1
-                        // 1.2.3-rc.2 => 1.2.3-rc.3
-                        num.unwrap_or(0) + 1
-                    } else {
-                        // Unknown level
-                        return Err(VersionError::InvalidLevelForPrerelease(
-                            level,
-                            self.0.pre.to_string(),
-                        ));
-                    }
-                } else {
-                    1
-                };
-                self.0.pre = Prerelease::new(&format!("{RC}.{new_num}"))?;
+            Level::Preminor => {
+                // 1.2.3 => 1.3.0-alpha.1
+                self.bump_minor();
+                self.start_pre(pre_label.unwrap_or(labels.first()))?;
+            }
+            Level::Prepatch => {
+                // 1.2.3 => 1.2.4-alpha.1
+                // 1.2.3-beta.2 => 1.2.4-alpha.1
+                self.0 = semver::Version::new(self.0.major, self.0.minor, self.0.patch + 1);
+                self.start_pre(pre_label.unwrap_or(labels.first()))?;
             }
+            Level::Prerelease => self.bump_prerelease(pre_label, labels)?,
             Level::Release => {
                 // 1.2.3-beta.1 => 1.2.3
                 self.0.pre = Prerelease::EMPTY;
@@ -99,6 +79,82 @@ impl SemverCustomValue {
         Ok(())
     }
 
+    /// Bump the pre-release to the given label, which needs to be part of `labels`.
+    ///
+    /// Bumping to the current label increments its number, bumping to a label further along the
+    /// chain restarts the numbering at 1, and bumping to an earlier label is an error. A release
+    /// gets the patch bumped first, except for `rc`, which is a candidate of the release itself.
+    fn bump_pre(&mut self, label: &str, labels: &PrereleaseLabels) -> Result<(), VersionError> {
+        let target = labels
+            .position(label)
+            .ok_or_else(|| VersionError::UnknownPrereleaseLabel(label.to_owned()))?;
+
+        let new_num = if let Some((current_label, num)) = self.pre_release_version_num()? {
+            match labels.position(&current_label) {
+                // 1.2.3-alpha => 1.2.3-alpha.1
+                // 1.2.3-alpha.1 => 1.2.3-alpha.2
+                Some(current) if current == target => num.unwrap_or(0) + 1,
+                // 1.2.3-alpha.2 => 1.2.3-beta.1
+                Some(current) if current < target => 1,
+                // Unknown label or trying to downgrade, e.g. from rc to beta
+                _ => {
+                    return Err(VersionError::InvalidLevelForPrerelease(
+                        label.to_owned(),
+                        self.0.pre.to_string(),
+                    ));
+                }
+            }
+        } else {
+            // 1.2.3 => 1.2.4-alpha.1
+            // 1.2.3 => 1.2.3-rc.1
+            if label != RC {
+                self.bump_patch();
+            }
+            1
+        };
+        self.0.pre = Prerelease::new(&format!("{label}.{new_num}"))?;
+        Ok(())
+    }
+
+    /// Increment the pre-release like npm's `prerelease` level, with any identifier.
+    fn bump_prerelease(
+        &mut self,
+        label: Option<&str>,
+        labels: &PrereleaseLabels,
+    ) -> Result<(), VersionError> {
+        if self.0.pre.is_empty() {
+            // 1.2.3 => 1.2.4-alpha.1
+            self.bump_patch();
+            return self.start_pre(label.unwrap_or(labels.first()));
+        }
+
+        let mut identifiers: Vec<String> = self.0.pre.split('.').map(str::to_owned).collect();
+        if let Some(label) = label
+            && identifiers[0] != label
+        {
+            // 1.2.3-alpha.2 => 1.2.3-beta.1
+            return self.start_pre(label);
+        }
+        match identifiers
+            .iter_mut()
+            .rev()
+            .find_map(|id| id.parse::<u64>().ok().map(|n| (id, n)))
+        {
+            // 1.2.3-alpha.1 => 1.2.3-alpha.2
+            // 1.2.3-4 => 1.2.3-5
+            Some((id, n)) => *id = (n + 1).to_string(),
+            // 1.2.3-alpha => 1.2.3-alpha.1
+            None => identifiers.push("1".into()),
+        }
+        self.0.pre = Prerelease::new(&identifiers.join("."))?;
+        Ok(())
+    }
+
+    fn start_pre(&mut self, label: &str) -> Result<(), VersionError> {
+        self.0.pre = Prerelease::new(&format!("{label}.1"))?;
+        Ok(())
+    }
+
     fn bump_major(&mut self) {
         // 1.2.3-foo+bar => 2.0.0
         self.0 = semver::Version::new(self.0.major + 1, 0, 0);
@@ -130,12 +186,35 @@ impl SemverCustomValue {
         }
     }
 
+    /// Convert a value into a semver value, coercing strings that aren't valid SemVer versions
+    /// if `lenient` is set.
+    #[allow(clippy::result_large_err)]
+    pub fn try_from_value(value: &Value, lenient: bool) -> Result<Self, ShellError> {
+        match value {
+            Value::String { val, .. } if lenient => coerce(val)
+                .map(|c| Self(c.version))
+                .ok_or_else(|| not_coercible_error(value.span())),
+            _ => value.try_into(),
+        }
+    }
+
     /// For use by tests and examples only.
     pub fn test_value(s: &str) -> Value {
         Value::test_custom_value(Box::new(Self(s.parse::<semver::Version>().unwrap())))
     }
 }
 
+/// Convert a version into a record of its components, as returned by `semver to-record`.
+pub fn version_record(version: &semver::Version, span: Span) -> Value {
+    let mut record = Record::new();
+    record.push("major", Value::int(version.major as i64, span));
+    record.push("minor", Value::int(version.minor as i64, span));
+    record.push("patch", Value::int(version.patch as i64, span));
+    record.push("pre", Value::string(version.pre.as_str(), span));
+    record.push("build", Value::string(version.build.as_str(), span));
+    Value::record(record, span)
+}
+
 #[typetag::serde]
 impl CustomValue for SemverCustomValue {
     fn clone_value(&self, span: Span) -> Value {
@@ -192,6 +271,16 @@ impl CustomValue for SemverCustomValue {
             .and_then(|cv| cv.as_any().downcast_ref())
             .and_then(|v: &SemverCustomValue| self.0.partial_cmp(&v.0))
     }
+
+    fn operation(
+        &self,
+        lhs_span: Span,
+        operator: Operator,
+        op: Span,
+        right: &Value,
+    ) -> Result<Value, ShellError> {
+        compare_operation(self, lhs_span, operator, op, right)
+    }
 }
 
 impl<'a> TryFrom<&'a Value> for SemverCustomValue {
@@ -239,3 +328,482 @@ impl Deref for SemverCustomValue {
         &self.0
     }
 }
+
+/// A version requirement, made of one or more alternatives separated by `||`. Cargo requirements
+/// always have a single alternative.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SemverReqCustomValue(pub Vec<semver::VersionReq>);
+
+impl SemverReqCustomValue {
+    pub fn into_value(self, span: Span) -> Value {
+        Value::custom(Box::new(self), span)
+    }
+
+    /// For use by tests and examples only.
+    pub fn test_value(s: &str) -> Value {
+        Value::test_custom_value(Box::new(Self(
+            s.split("||")
+                .map(|req| req.trim().parse::<semver::VersionReq>().unwrap())
+                .collect(),
+        )))
+    }
+
+    /// Accepts either a requirement string, parsed with the given dialect, or a `semver-req`
+    /// custom value.
+    #[allow(clippy::result_large_err)]
+    pub fn try_from_value(value: &Value, dialect: Dialect) -> Result<Self, ShellError> {
+        let span = value.span();
+
+        match value {
+            Value::String { val, .. } => {
+                dialect
+                    .parse(val)
+                    .map(Self)
+                    .map_err(|e| ShellError::IncorrectValue {
+                        msg: format!("Value is not a valid semver requirement: {e}"),
+                        val_span: span,
+                        call_span: span,
+                    })
+            }
+            Value::Custom { val, .. } => {
+                if let Some(req) = val.as_any().downcast_ref::<Self>() {
+                    Ok(req.clone())
+                } else {
+                    Err(ShellError::CantConvert {
+                        to_type: "semver-req".into(),
+                        from_type: val.type_name(),
+                        span,
+                        help: None,
+                    })
+                }
+            }
+            x => Err(ShellError::CantConvert {
+                to_type: "semver-req".into(),
+                from_type: x.get_type().to_string(),
+                span,
+                help: None,
+            }),
+        }
+    }
+
+    /// The comparators of the requirement, as a list of records. Requirements with several
+    /// alternatives give a list of such lists, one per alternative.
+    fn comparators_value(&self, span: Span) -> Value {
+        match self.0.as_slice() {
+            [req] => comparators_value(req, span),
+            reqs => Value::list(
+                reqs.iter()
+                    .map(|req| comparators_value(req, span))
+                    .collect(),
+                span,
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for SemverReqCustomValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let alternatives: Vec<String> = self.0.iter().map(|req| req.to_string()).collect();
+        f.write_str(&alternatives.join(" || "))
+    }
+}
+
+fn comparators_value(req: &semver::VersionReq, span: Span) -> Value {
+    let comparators = req
+        .comparators
+        .iter()
+        .map(|c| {
+            let mut record = Record::new();
+            record.push("op", Value::string(op_symbol(c.op), span));
+            record.push("major", Value::int(c.major as i64, span));
+            record.push(
+                "minor",
+                c.minor
+                    .map_or(Value::nothing(span), |n| Value::int(n as i64, span)),
+            );
+            record.push(
+                "patch",
+                c.patch
+                    .map_or(Value::nothing(span), |n| Value::int(n as i64, span)),
+            );
+            record.push("pre", Value::string(c.pre.to_string(), span));
+            Value::record(record, span)
+        })
+        .collect();
+    Value::list(comparators, span)
+}
+
+/// Returns the textual operator used for a comparator in a version requirement.
+pub fn op_symbol(op: Op) -> &'static str {
+    match op {
+        Op::Exact => "=",
+        Op::Greater => ">",
+        Op::GreaterEq => ">=",
+        Op::Less => "<",
+        Op::LessEq => "<=",
+        Op::Tilde => "~",
+        Op::Caret => "^",
+        Op::Wildcard => "*",
+        _ => "?",
+    }
+}
+
+#[typetag::serde]
+impl CustomValue for SemverReqCustomValue {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::custom(Box::new(self.clone()), span)
+    }
+
+    fn type_name(&self) -> String {
+        "semver-req".to_string()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        Ok(Value::string(self.to_string(), span))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn follow_path_string(
+        &self,
+        self_span: Span,
+        column_name: String,
+        path_span: Span,
+        _optional: bool,
+        casing: Casing,
+    ) -> Result<Value, ShellError> {
+        let col = match casing {
+            Casing::Sensitive => column_name,
+            Casing::Insensitive => column_name.to_lowercase(),
+        };
+
+        match col.as_str() {
+            "comparators" => Ok(self.comparators_value(path_span)),
+            _ => Err(ShellError::CantFindColumn {
+                col_name: col,
+                span: Some(path_span),
+                src_span: self_span,
+            }),
+        }
+    }
+
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        // Requirements aren't ordered, but equal requirements should still compare as such.
+        other
+            .as_custom_value()
+            .ok()
+            .and_then(|cv| cv.as_any().downcast_ref())
+            .and_then(|v: &SemverReqCustomValue| (self.0 == v.0).then_some(Ordering::Equal))
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for SemverReqCustomValue {
+    type Error = ShellError;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        Self::try_from_value(value, Dialect::Cargo)
+    }
+}
+
+/// A Python package version, following PEP 440.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Pep440CustomValue(pub Pep440Version);
+
+impl Pep440CustomValue {
+    pub fn into_value(self, span: Span) -> Value {
+        Value::custom(Box::new(self), span)
+    }
+
+    /// For use by tests and examples only.
+    pub fn test_value(s: &str) -> Value {
+        Value::test_custom_value(Box::new(Self(Pep440Version::parse(s).unwrap())))
+    }
+}
+
+#[typetag::serde]
+impl CustomValue for Pep440CustomValue {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::custom(Box::new(self.clone()), span)
+    }
+
+    fn type_name(&self) -> String {
+        "pep440".to_string()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        Ok(Value::string(self.0.to_string(), span))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn follow_path_string(
+        &self,
+        self_span: Span,
+        column_name: String,
+        path_span: Span,
+        _optional: bool,
+        casing: Casing,
+    ) -> Result<Value, ShellError> {
+        let col = match casing {
+            Casing::Sensitive => column_name,
+            Casing::Insensitive => column_name.to_lowercase(),
+        };
+        let int_or_nothing = |n: Option<u64>| {
+            n.map_or(Value::nothing(path_span), |n| {
+                Value::int(n as i64, path_span)
+            })
+        };
+
+        match col.as_str() {
+            "epoch" => Ok(Value::int(self.0.epoch as i64, path_span)),
+            "release" => Ok(Value::list(
+                self.0
+                    .release
+                    .iter()
+                    .map(|n| Value::int(*n as i64, path_span))
+                    .collect(),
+                path_span,
+            )),
+            "pre" => Ok(self.0.pre.map_or(Value::nothing(path_span), |(kind, n)| {
+                Value::string(format!("{kind}{n}"), path_span)
+            })),
+            "post" => Ok(int_or_nothing(self.0.post)),
+            "dev" => Ok(int_or_nothing(self.0.dev)),
+            "local" => Ok(if self.0.local.is_empty() {
+                Value::nothing(path_span)
+            } else {
+                let local: Vec<String> = self.0.local.iter().map(ToString::to_string).collect();
+                Value::string(local.join("."), path_span)
+            }),
+            _ => Err(ShellError::CantFindColumn {
+                col_name: col,
+                span: Some(path_span),
+                src_span: self_span,
+            }),
+        }
+    }
+
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        other
+            .as_custom_value()
+            .ok()
+            .and_then(|cv| cv.as_any().downcast_ref())
+            .and_then(|v: &Pep440CustomValue| self.0.partial_cmp(&v.0))
+    }
+
+    fn operation(
+        &self,
+        lhs_span: Span,
+        operator: Operator,
+        op: Span,
+        right: &Value,
+    ) -> Result<Value, ShellError> {
+        compare_operation(self, lhs_span, operator, op, right)
+    }
+}
+
+/// Evaluate a comparison operator between a version and another value of the same type, using
+/// the ordering of the version.
+#[allow(clippy::result_large_err)]
+fn compare_operation(
+    value: &dyn CustomValue,
+    lhs_span: Span,
+    operator: Operator,
+    op: Span,
+    right: &Value,
+) -> Result<Value, ShellError> {
+    let Some(ordering) = value.partial_cmp(right) else {
+        return Err(ShellError::OperatorUnsupportedType {
+            op: operator,
+            unsupported: right.get_type(),
+            op_span: op,
+            unsupported_span: right.span(),
+            help: None,
+        });
+    };
+    let result = match operator {
+        Operator::Comparison(Comparison::Equal) => ordering.is_eq(),
+        Operator::Comparison(Comparison::NotEqual) => ordering.is_ne(),
+        Operator::Comparison(Comparison::LessThan) => ordering.is_lt(),
+        Operator::Comparison(Comparison::LessThanOrEqual) => ordering.is_le(),
+        Operator::Comparison(Comparison::GreaterThan) => ordering.is_gt(),
+        Operator::Comparison(Comparison::GreaterThanOrEqual) => ordering.is_ge(),
+        _ => {
+            return Err(ShellError::OperatorUnsupportedType {
+                op: operator,
+                unsupported: Type::Custom(value.type_name().into()),
+                op_span: op,
+                unsupported_span: lhs_span,
+                help: None,
+            });
+        }
+    };
+    Ok(Value::bool(result, op))
+}
+
+impl<'a> TryFrom<&'a Value> for Pep440CustomValue {
+    type Error = ShellError;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        let span = value.span();
+
+        match value {
+            Value::String { val, .. } => {
+                Pep440Version::parse(val)
+                    .map(Self)
+                    .map_err(|e| ShellError::IncorrectValue {
+                        msg: e.to_string(),
+                        val_span: span,
+                        call_span: span,
+                    })
+            }
+            Value::Custom { val, .. } => {
+                if let Some(version) = val.as_any().downcast_ref::<Self>() {
+                    Ok(version.clone())
+                } else {
+                    Err(ShellError::CantConvert {
+                        to_type: "pep440".into(),
+                        from_type: val.type_name(),
+                        span,
+                        help: None,
+                    })
+                }
+            }
+            x => Err(ShellError::CantConvert {
+                to_type: "pep440".into(),
+                from_type: x.get_type().to_string(),
+                span,
+                help: None,
+            }),
+        }
+    }
+}
+
+/// A calendar version, along with the format it follows.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CalverCustomValue(pub CalverVersion);
+
+impl CalverCustomValue {
+    pub fn into_value(self, span: Span) -> Value {
+        Value::custom(Box::new(self), span)
+    }
+
+    /// For use by tests and examples only.
+    pub fn test_value(s: &str, format: &str) -> Value {
+        let format = CalverFormat::parse(format).unwrap();
+        Value::test_custom_value(Box::new(Self(CalverVersion::parse(s, &format).unwrap())))
+    }
+
+    /// Accepts either a string following the given format, or a `calver` custom value.
+    #[allow(clippy::result_large_err)]
+    pub fn try_from_value(value: &Value, format: &CalverFormat) -> Result<Self, ShellError> {
+        let span = value.span();
+
+        match value {
+            Value::String { val, .. } => CalverVersion::parse(val, format).map(Self).map_err(|e| {
+                ShellError::IncorrectValue {
+                    msg: e.to_string(),
+                    val_span: span,
+                    call_span: span,
+                }
+            }),
+            Value::Custom { val, .. } => {
+                if let Some(version) = val.as_any().downcast_ref::<Self>() {
+                    Ok(version.clone())
+                } else {
+                    Err(ShellError::CantConvert {
+                        to_type: "calver".into(),
+                        from_type: val.type_name(),
+                        span,
+                        help: None,
+                    })
+                }
+            }
+            x => Err(ShellError::CantConvert {
+                to_type: "calver".into(),
+                from_type: x.get_type().to_string(),
+                span,
+                help: None,
+            }),
+        }
+    }
+}
+
+#[typetag::serde]
+impl CustomValue for CalverCustomValue {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::custom(Box::new(self.clone()), span)
+    }
+
+    fn type_name(&self) -> String {
+        "calver".to_string()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        Ok(Value::string(self.0.to_string(), span))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn follow_path_string(
+        &self,
+        self_span: Span,
+        column_name: String,
+        path_span: Span,
+        _optional: bool,
+        casing: Casing,
+    ) -> Result<Value, ShellError> {
+        let col = match casing {
+            Casing::Sensitive => column_name,
+            Casing::Insensitive => column_name.to_lowercase(),
+        };
+
+        if col == "format" {
+            return Ok(Value::string(self.0.format.to_string(), path_span));
+        }
+        match self.0.segment(&col) {
+            Some(n) => Ok(Value::int(n as i64, path_span)),
+            None => Err(ShellError::CantFindColumn {
+                col_name: col,
+                span: Some(path_span),
+                src_span: self_span,
+            }),
+        }
+    }
+
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        other
+            .as_custom_value()
+            .ok()
+            .and_then(|cv| cv.as_any().downcast_ref())
+            .and_then(|v: &CalverCustomValue| self.0.partial_cmp(&v.0))
+    }
+
+    fn operation(
+        &self,
+        lhs_span: Span,
+        operator: Operator,
+        op: Span,
+        right: &Value,
+    ) -> Result<Value, ShellError> {
+        compare_operation(self, lhs_span, operator, op, right)
+    }
+}
@@ -0,0 +1,154 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::version::VersionError;
+
+/// A Debian package version, e.g. `1:2.3.4-0ubuntu1~bpo`, made of an optional epoch, the
+/// upstream version, and an optional Debian revision.
+#[derive(Debug, Clone)]
+pub struct DebVersion {
+    pub epoch: u64,
+    pub upstream: String,
+    pub revision: Option<String>,
+}
+
+impl DebVersion {
+    pub fn parse(input: &str) -> Result<Self, VersionError> {
+        let invalid =
+            |reason: &str| VersionError::InvalidDebVersion(input.to_owned(), reason.to_owned());
+
+        let s = input.trim();
+        let (epoch, rest) = match s.split_once(':') {
+            Some((epoch, rest)) => (
+                epoch
+                    .parse()
+                    .map_err(|_| invalid("the epoch should be a number"))?,
+                rest,
+            ),
+            None => (0, s),
+        };
+        let (upstream, revision) = match rest.rsplit_once('-') {
+            Some((upstream, revision)) => (upstream, Some(revision)),
+            None => (rest, None),
+        };
+
+        if upstream.is_empty() {
+            return Err(invalid("the upstream version is empty"));
+        }
+        if !upstream
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ".+~-:".contains(c))
+        {
+            return Err(invalid("invalid character in the upstream version"));
+        }
+        if let Some(revision) = revision
+            && (revision.is_empty()
+                || !revision
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || ".+~".contains(c)))
+        {
+            return Err(invalid("invalid Debian revision"));
+        }
+
+        Ok(Self {
+            epoch,
+            upstream: upstream.to_owned(),
+            revision: revision.map(ToOwned::to_owned),
+        })
+    }
+}
+
+impl Ord for DebVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| verrevcmp(&self.upstream, &other.upstream))
+            .then_with(|| {
+                verrevcmp(
+                    self.revision.as_deref().unwrap_or(""),
+                    other.revision.as_deref().unwrap_or(""),
+                )
+            })
+    }
+}
+
+impl PartialOrd for DebVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for DebVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for DebVersion {}
+
+impl fmt::Display for DebVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}:", self.epoch)?;
+        }
+        f.write_str(&self.upstream)?;
+        if let Some(revision) = &self.revision {
+            write!(f, "-{revision}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The weight of a character in the non-digit parts of a version: `~` sorts before anything, even
+/// the end of the string, and letters sort before other characters.
+fn order(c: Option<u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(c) if c.is_ascii_digit() => 0,
+        Some(c) if c.is_ascii_alphabetic() => c as i32,
+        Some(b'~') => -1,
+        Some(c) => c as i32 + 256,
+    }
+}
+
+/// Compare two upstream versions or revisions, like dpkg's `verrevcmp`: non-digit parts are
+/// compared character by character, and digit parts numerically.
+fn verrevcmp(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    let is_digit = |s: &[u8], k: usize| s.get(k).is_some_and(u8::is_ascii_digit);
+
+    while i < a.len() || j < b.len() {
+        while (i < a.len() && !is_digit(a, i)) || (j < b.len() && !is_digit(b, j)) {
+            let (ac, bc) = (order(a.get(i).copied()), order(b.get(j).copied()));
+            if ac != bc {
+                return ac.cmp(&bc);
+            }
+            i += 1;
+            j += 1;
+        }
+        while a.get(i) == Some(&b'0') {
+            i += 1;
+        }
+        while b.get(j) == Some(&b'0') {
+            j += 1;
+        }
+        let mut first_diff = Ordering::Equal;
+        while is_digit(a, i) && is_digit(b, j) {
+            if first_diff.is_eq() {
+                first_diff = a[i].cmp(&b[j]);
+            }
+            i += 1;
+            j += 1;
+        }
+        if is_digit(a, i) {
+            return Ordering::Greater;
+        }
+        if is_digit(b, j) {
+            return Ordering::Less;
+        }
+        if first_diff.is_ne() {
+            return first_diff;
+        }
+    }
+    Ordering::Equal
+}
@@ -0,0 +1,270 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::{
+    conventional::{self, ConventionalCommit},
+    custom_value::SemverCustomValue,
+    version::{PrereleaseLabels, VersionError},
+};
+
+/// A local git repository, accessed through the `git` command.
+pub struct Repository {
+    path: PathBuf,
+}
+
+/// A tag of the repository.
+#[derive(Debug, Clone)]
+pub struct Tag {
+    pub name: String,
+    /// The commit the tag points to.
+    pub commit: String,
+    /// The date of the tag (or of the commit, for lightweight tags).
+    pub date: Option<DateTime<FixedOffset>>,
+}
+
+impl Tag {
+    /// The version of the tag, if its name is a SemVer version once the prefix is removed.
+    /// Without a prefix, an optional `v` is accepted.
+    pub fn version(&self, prefix: Option<&str>) -> Option<semver::Version> {
+        let version = match prefix {
+            Some(prefix) => self.name.strip_prefix(prefix)?,
+            None => self.name.strip_prefix('v').unwrap_or(&self.name),
+        };
+        semver::Version::parse(version).ok()
+    }
+}
+
+impl Repository {
+    pub fn open(path: &Path) -> Result<Self, VersionError> {
+        let repo = Self {
+            path: path.to_owned(),
+        };
+        let toplevel = repo.run(&["rev-parse", "--show-toplevel"])?;
+        Ok(Self {
+            path: PathBuf::from(toplevel.trim()),
+        })
+    }
+
+    /// The tags of the repository. If `merged` is set, only the tags reachable from `HEAD` are
+    /// returned.
+    pub fn tags(&self, merged: bool) -> Result<Vec<Tag>, VersionError> {
+        let mut args = vec![
+            "for-each-ref",
+            "--format=%(refname:strip=2)%00%(objectname)%00%(*objectname)%00%(creatordate:iso-strict)",
+        ];
+        if merged {
+            args.extend(["--merged", "HEAD"]);
+        }
+        args.push("refs/tags");
+
+        Ok(self
+            .run(&args)?
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\0').collect();
+                let [name, object, target, date] = fields[..] else {
+                    return None;
+                };
+                Some(Tag {
+                    name: name.to_owned(),
+                    // Annotated tags point to a tag object, which points to the commit
+                    commit: if target.is_empty() { object } else { target }.to_owned(),
+                    date: DateTime::parse_from_rfc3339(date).ok(),
+                })
+            })
+            .collect())
+    }
+
+    /// The tags that are SemVer versions, with their versions, sorted by precedence.
+    pub fn version_tags(
+        &self,
+        prefix: Option<&str>,
+        merged: bool,
+    ) -> Result<Vec<(semver::Version, Tag)>, VersionError> {
+        let mut tags: Vec<_> = self
+            .tags(merged)?
+            .into_iter()
+            .filter_map(|tag| Some((tag.version(prefix)?, tag)))
+            .collect();
+        tags.sort_by(|(a, _), (b, _)| a.cmp_precedence(b));
+        Ok(tags)
+    }
+
+    /// The messages of the commits reachable from `HEAD`, but not from `since`.
+    pub fn commit_messages(&self, since: Option<&str>) -> Result<Vec<String>, VersionError> {
+        let range = match since {
+            Some(since) => format!("{since}..HEAD"),
+            None => "HEAD".to_owned(),
+        };
+        let log = self.run(&["log", "--format=%B%x00", &range])?;
+        Ok(log
+            .split('\0')
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+            .map(ToOwned::to_owned)
+            .collect())
+    }
+
+    /// The next version, from the latest version tag reachable from `HEAD` (or `0.0.0`) and the
+    /// Conventional Commits since then. Returns the latest version if no commit requires a bump.
+    pub fn next_version(
+        &self,
+        prefix: Option<&str>,
+        labels: &PrereleaseLabels,
+    ) -> Result<semver::Version, VersionError> {
+        let tags = self.version_tags(prefix, true)?;
+        let latest = tags.last();
+        let commits: Vec<ConventionalCommit> = self
+            .commit_messages(latest.map(|(_, tag)| tag.name.as_str()))?
+            .iter()
+            .filter_map(|message| ConventionalCommit::parse(message))
+            .collect();
+
+        let mut version = SemverCustomValue(
+            latest.map_or(semver::Version::new(0, 0, 0), |(version, _)| {
+                version.clone()
+            }),
+        );
+        if let Some(level) = conventional::summary_level(&commits) {
+            let level = conventional::adjust_for_initial_development(level, &version.0);
+            version.bump(level, None, labels, None)?;
+        }
+        Ok(version.0)
+    }
+
+    fn run(&self, args: &[&str]) -> Result<String, VersionError> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(args)
+            .output()
+            .map_err(|e| VersionError::Git(format!("failed to run git: {e}")))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(VersionError::Git(
+                String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+    use crate::test_support::TempDir;
+
+    /// A throwaway repository in the temporary directory.
+    struct TestRepo(TempDir);
+
+    impl TestRepo {
+        fn new(name: &str) -> Self {
+            let repo = Self(TempDir::new(name));
+            repo.git(&["init", "--quiet"]);
+            repo
+        }
+
+        fn git(&self, args: &[&str]) {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(self.0.path())
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(["-c", "commit.gpgsign=false", "-c", "tag.gpgsign=false"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        }
+
+        fn commit(&self, message: &str) {
+            self.git(&["commit", "--quiet", "--allow-empty", "-m", message]);
+        }
+
+        fn next(&self, prefix: Option<&str>) -> semver::Version {
+            Repository::open(self.0.path())
+                .unwrap()
+                .next_version(prefix, &PrereleaseLabels::default())
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn next_version_from_conventional_commits() {
+        let repo = TestRepo::new("next");
+        repo.commit("feat: initial features");
+        repo.git(&["tag", "v1.2.3"]);
+        repo.git(&["tag", "not-a-version"]);
+        assert_eq!(repo.next(None), semver::Version::new(1, 2, 3));
+
+        repo.commit("fix(parser): handle empty input");
+        assert_eq!(repo.next(None), semver::Version::new(1, 2, 4));
+
+        repo.commit("docs: typo");
+        repo.commit("feat: add ranges");
+        assert_eq!(repo.next(None), semver::Version::new(1, 3, 0));
+
+        repo.commit("refactor: rename things\n\nBREAKING CHANGE: the API changed");
+        assert_eq!(repo.next(None), semver::Version::new(2, 0, 0));
+    }
+
+    #[test]
+    fn next_version_with_prefix_and_initial_development() {
+        let repo = TestRepo::new("prefix");
+        repo.commit("feat: initial features");
+        repo.git(&["tag", "-a", "-m", "release", "my-crate-v0.4.1"]);
+        repo.git(&["tag", "v9.0.0"]);
+        repo.commit("feat(api)!: remove the old API");
+        assert_eq!(repo.next(Some("my-crate-v")), semver::Version::new(0, 5, 0));
+
+        let tags = Repository::open(repo.0.path())
+            .unwrap()
+            .version_tags(Some("my-crate-v"), false)
+            .unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].0, semver::Version::new(0, 4, 1));
+        assert_eq!(tags[0].1.name, "my-crate-v0.4.1");
+        assert!(tags[0].1.date.is_some());
+    }
+
+    #[test]
+    fn next_version_outside_a_repository() {
+        let dir = TempDir::new("not-a-repo");
+        let name = dir.path().file_name().unwrap().to_str().unwrap();
+
+        let mut plugin =
+            nu_plugin_test_support::PluginTest::new("semver", crate::SemverPlugin.into()).unwrap();
+        plugin.engine_state_mut().add_env_var(
+            "PWD".into(),
+            nu_protocol::Value::test_string(std::env::temp_dir().display().to_string()),
+        );
+        // The path is relative to the current directory
+        let source = format!("semver git next '{name}'");
+        let result = plugin.eval(&source);
+
+        let Err(nu_protocol::ShellError::LabeledError(error)) = result else {
+            panic!("expected a labeled error, got {result:?}");
+        };
+        assert_eq!(error.msg, "Semver error");
+        let [label] = &error.labels[..] else {
+            panic!("expected a single label, got {:?}", error.labels);
+        };
+        assert!(
+            label.text.contains("not a git repository"),
+            "{}",
+            label.text
+        );
+        // The label points at the path argument
+        let start = source.find('\'').unwrap();
+        assert_eq!(
+            label.span,
+            nu_protocol::Span::new(start, source.len()),
+            "{source}"
+        );
+    }
+}
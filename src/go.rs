@@ -0,0 +1,113 @@
+use crate::version::VersionError;
+
+/// Parse a Go module version, e.g. `v1.2.3` or `v2.0.0+incompatible`, into the equivalent SemVer
+/// version. Like `golang.org/x/mod/semver`, the `v` prefix is required, and the shorthands `v1`
+/// and `v1.2` stand for `v1.0.0` and `v1.2.0`.
+pub fn parse(input: &str) -> Result<semver::Version, VersionError> {
+    let invalid =
+        |reason: &str| VersionError::InvalidGoVersion(input.to_owned(), reason.to_owned());
+
+    let version = input
+        .strip_prefix('v')
+        .ok_or_else(|| invalid("Go versions should start with 'v'"))?;
+    let full = match version
+        .split(['-', '+'])
+        .next()
+        .map(|core| core.split('.').count())
+    {
+        Some(1) if !version.contains(['-', '+']) => format!("{version}.0.0"),
+        Some(2) if !version.contains(['-', '+']) => format!("{version}.0"),
+        Some(3) => version.to_owned(),
+        _ => return Err(invalid("expected vMAJOR[.MINOR[.PATCH]]")),
+    };
+    semver::Version::parse(&full).map_err(|e| invalid(&e.to_string()))
+}
+
+/// The canonical form of a Go version: the `v` prefix followed by the full version. Build metadata
+/// is dropped, except for `+incompatible`.
+pub fn canonical(version: &semver::Version) -> String {
+    let mut canonical = semver::Version {
+        build: semver::BuildMetadata::EMPTY,
+        ..version.clone()
+    };
+    if is_incompatible(version) {
+        canonical.build = version.build.clone();
+    }
+    format!("v{canonical}")
+}
+
+/// Whether the version has the `+incompatible` suffix, used for major versions above 1 of modules
+/// without a `go.mod` file.
+pub fn is_incompatible(version: &semver::Version) -> bool {
+    version.build.as_str() == "incompatible"
+}
+
+/// The parts of a Go pseudo-version, e.g. `v1.2.4-0.20191109021931-daa7c04131f5`.
+pub struct PseudoVersion {
+    /// The version the pseudo-version is based on, if any (`v1.2.3` in the example above).
+    pub base: Option<semver::Version>,
+    /// The commit time, in UTC, formatted as `yyyymmddhhmmss`.
+    pub timestamp: String,
+    /// The commit hash prefix.
+    pub revision: String,
+}
+
+impl PseudoVersion {
+    /// Split a pseudo-version into its parts, if the version is one. There are 3 forms of
+    /// pseudo-versions:
+    /// - `vX.0.0-yyyymmddhhmmss-abcdefabcdef`, when there is no base version,
+    /// - `vX.Y.Z-pre.0.yyyymmddhhmmss-abcdefabcdef`, when the base is the pre-release `vX.Y.Z-pre`,
+    /// - `vX.Y.(Z+1)-0.yyyymmddhhmmss-abcdefabcdef`, when the base is the release `vX.Y.Z`.
+    pub fn from_version(version: &semver::Version) -> Option<Self> {
+        let pre = version.pre.as_str();
+        let (prefix, last) = match pre.rsplit_once('.') {
+            Some((prefix, last)) => (Some(prefix), last),
+            None => (None, pre),
+        };
+        let (timestamp, revision) = last.split_once('-')?;
+        if timestamp.len() != 14
+            || !timestamp.chars().all(|c| c.is_ascii_digit())
+            || revision.is_empty()
+            || !revision.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return None;
+        }
+
+        let base = match prefix {
+            None if version.minor == 0 && version.patch == 0 => None,
+            None => return None,
+            Some("0") if version.patch > 0 => Some(semver::Version::new(
+                version.major,
+                version.minor,
+                version.patch - 1,
+            )),
+            Some(prefix) => {
+                let pre = prefix.strip_suffix(".0")?;
+                Some(semver::Version {
+                    pre: semver::Prerelease::new(pre).ok()?,
+                    ..semver::Version::new(version.major, version.minor, version.patch)
+                })
+            }
+        };
+
+        Some(Self {
+            base,
+            timestamp: timestamp.to_owned(),
+            revision: revision.to_owned(),
+        })
+    }
+
+    /// The commit time as an RFC 3339 timestamp, e.g. `2019-11-09T02:19:31Z`.
+    pub fn time(&self) -> String {
+        let t = &self.timestamp;
+        format!(
+            "{}-{}-{}T{}:{}:{}Z",
+            &t[..4],
+            &t[4..6],
+            &t[6..8],
+            &t[8..10],
+            &t[10..12],
+            &t[12..]
+        )
+    }
+}
@@ -1,8 +1,33 @@
-use nu_plugin::{MsgPackSerializer, Plugin};
+use std::cmp::Ordering;
 
+use nu_plugin::{EngineInterface, MsgPackSerializer, Plugin};
+use nu_protocol::{CustomValue, LabeledError, Spanned, Value};
+
+use crate::{
+    config::{BaseValue, SemverConfig},
+    custom_value::{SemverCustomValue, version_record},
+};
+
+mod calver;
+mod changelog;
 mod commands;
+mod config;
+mod conventional;
 mod custom_value;
+mod deb;
+mod git;
+mod go;
+mod manifest;
+mod maven;
+mod npm;
+mod pep440;
+mod range;
+mod rpm;
+mod scheme;
+#[cfg(test)]
+mod test_support;
 mod version;
+mod workspace;
 
 pub struct SemverPlugin;
 
@@ -14,6 +39,43 @@ impl Plugin for SemverPlugin {
     fn version(&self) -> String {
         env!("CARGO_PKG_VERSION").into()
     }
+
+    fn custom_value_to_base_value(
+        &self,
+        engine: &EngineInterface,
+        custom_value: Spanned<Box<dyn CustomValue>>,
+    ) -> Result<Value, LabeledError> {
+        let span = custom_value.span;
+        if let Some(version) = custom_value
+            .item
+            .as_any()
+            .downcast_ref::<SemverCustomValue>()
+            && SemverConfig::from_engine_or_default(engine)?.base_value == BaseValue::Record
+        {
+            return Ok(version_record(version, span));
+        }
+        Ok(custom_value.item.to_base_value(span)?)
+    }
+
+    fn custom_value_partial_cmp(
+        &self,
+        engine: &EngineInterface,
+        custom_value: Box<dyn CustomValue>,
+        other_value: Value,
+    ) -> Result<Option<Ordering>, LabeledError> {
+        let this = custom_value.as_any().downcast_ref::<SemverCustomValue>();
+        let other = other_value
+            .as_custom_value()
+            .ok()
+            .and_then(|cv| cv.as_any().downcast_ref::<SemverCustomValue>());
+        match (this, other) {
+            (Some(this), Some(other)) => {
+                let config = SemverConfig::from_engine_or_default(engine)?;
+                Ok(Some(config.cmp_versions(this, other)))
+            }
+            _ => Ok(custom_value.partial_cmp(&other_value)),
+        }
+    }
 }
 
 fn main() {
@@ -0,0 +1,506 @@
+use std::{
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use toml_edit::{DocumentMut, Item};
+
+use crate::version::VersionError;
+
+/// The manifest file names, in the order they are looked for in a directory.
+const MANIFEST_NAMES: [&str; 3] = ["Cargo.toml", "package.json", "pyproject.toml"];
+
+/// The version shared by the members of a Cargo workspace.
+pub const WORKSPACE_VERSION: &[&str] = &["workspace", "package", "version"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestKind {
+    Cargo,
+    Npm,
+    Pyproject,
+}
+
+impl ManifestKind {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "Cargo.toml" => Some(Self::Cargo),
+            "package.json" => Some(Self::Npm),
+            "pyproject.toml" => Some(Self::Pyproject),
+            _ => None,
+        }
+    }
+
+    /// The places where the version can be found, in order of preference.
+    fn version_paths(self) -> &'static [&'static [&'static str]] {
+        match self {
+            Self::Cargo => &[&["package", "version"], WORKSPACE_VERSION],
+            Self::Npm => &[&["version"]],
+            Self::Pyproject => &[&["project", "version"], &["tool", "poetry", "version"]],
+        }
+    }
+}
+
+/// A project manifest (`Cargo.toml`, `package.json` or `pyproject.toml`), whose version can be
+/// read and edited without touching the rest of the file.
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    pub path: PathBuf,
+    pub kind: ManifestKind,
+    contents: String,
+}
+
+impl Manifest {
+    /// Open a manifest, given either its path or the directory containing it.
+    pub fn open(path: &Path) -> Result<Self, VersionError> {
+        let path = if path.is_dir() {
+            MANIFEST_NAMES
+                .iter()
+                .map(|name| path.join(name))
+                .find(|p| p.is_file())
+                .ok_or_else(|| invalid(path, "no Cargo.toml, package.json or pyproject.toml"))?
+        } else {
+            path.to_owned()
+        };
+        let kind = ManifestKind::from_path(&path).ok_or_else(|| {
+            invalid(
+                &path,
+                "expected a Cargo.toml, package.json or pyproject.toml file",
+            )
+        })?;
+        let contents = fs::read_to_string(&path).map_err(|e| invalid(&path, &e.to_string()))?;
+        Ok(Self {
+            path,
+            kind,
+            contents,
+        })
+    }
+
+    /// The manifest actually holding the version: for a Cargo package with
+    /// `version.workspace = true`, this is the manifest of the workspace root.
+    pub fn version_source(self) -> Result<Self, VersionError> {
+        if self.kind != ManifestKind::Cargo || !self.inherits_version()? {
+            return Ok(self);
+        }
+        let mut dir = self.path.parent();
+        while let Some(current) = dir {
+            let candidate = current.join("Cargo.toml");
+            if candidate.is_file() {
+                let manifest = Self::open(&candidate)?;
+                if manifest.toml()?.contains_key("workspace") {
+                    return Ok(manifest);
+                }
+            }
+            dir = current.parent();
+        }
+        Err(invalid(
+            &self.path,
+            "the version is inherited, but the workspace root can't be found",
+        ))
+    }
+
+    /// Whether this is a Cargo package with `version.workspace = true`.
+    pub fn inherits_version(&self) -> Result<bool, VersionError> {
+        if self.kind != ManifestKind::Cargo {
+            return Ok(false);
+        }
+        let doc = self.toml()?;
+        Ok(get(doc.as_item(), &["package", "version"])
+            .and_then(Item::as_table_like)
+            .and_then(|version| version.get("workspace"))
+            .and_then(Item::as_bool)
+            .unwrap_or(false))
+    }
+
+    /// Get the version string of the manifest.
+    pub fn version(&self) -> Result<String, VersionError> {
+        if self.kind == ManifestKind::Npm {
+            let span = json_version_span(&self.contents)
+                .ok_or_else(|| invalid(&self.path, "no top-level version string"))?;
+            return Ok(self.contents[span].to_owned());
+        }
+        let doc = self.toml()?;
+        self.check_dynamic(&doc)?;
+        self.version_at(self.resolved_version_path(&doc)?)
+    }
+
+    /// Get the version string at the given path of a TOML manifest.
+    pub fn version_at(&self, path: &[&str]) -> Result<String, VersionError> {
+        get(self.toml()?.as_item(), path)
+            .and_then(Item::as_str)
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| invalid(&self.path, &format!("no {} field", path.join("."))))
+    }
+
+    /// Replace the version string of the manifest, keeping the formatting of the rest of the
+    /// file. The change is only written to disk by `save`.
+    pub fn set_version(&mut self, version: &str) -> Result<(), VersionError> {
+        if self.kind == ManifestKind::Npm {
+            let span = json_version_span(&self.contents)
+                .ok_or_else(|| invalid(&self.path, "no top-level version string"))?;
+            self.contents.replace_range(span, version);
+            return Ok(());
+        }
+        let doc = self.toml()?;
+        self.check_dynamic(&doc)?;
+        self.set_version_at(self.resolved_version_path(&doc)?, version)
+    }
+
+    /// Replace the version string at the given path of a TOML manifest, keeping the formatting
+    /// of the rest of the file.
+    pub fn set_version_at(&mut self, path: &[&str], version: &str) -> Result<(), VersionError> {
+        let mut doc = self.toml()?;
+        let item = get_mut(doc.as_item_mut(), path)
+            .filter(|item| item.is_str())
+            .and_then(Item::as_value_mut)
+            .ok_or_else(|| invalid(&self.path, &format!("no {} field", path.join("."))))?;
+        replace_str(item, version);
+        self.set_toml(&doc);
+        Ok(())
+    }
+
+    /// The name of a Cargo package, if the manifest has a `[package]` section.
+    pub fn package_name(&self) -> Result<Option<String>, VersionError> {
+        Ok(get(self.toml()?.as_item(), &["package", "name"])
+            .and_then(Item::as_str)
+            .map(ToOwned::to_owned))
+    }
+
+    pub fn save(&self) -> Result<(), VersionError> {
+        fs::write(&self.path, &self.contents).map_err(|e| invalid(&self.path, &e.to_string()))
+    }
+
+    /// Parse the manifest as a TOML document.
+    pub fn toml(&self) -> Result<DocumentMut, VersionError> {
+        self.contents
+            .parse()
+            .map_err(|e: toml_edit::TomlError| invalid(&self.path, e.message()))
+    }
+
+    /// The path of the version string in the document.
+    fn version_path(&self, doc: &DocumentMut) -> Option<&'static [&'static str]> {
+        self.kind
+            .version_paths()
+            .iter()
+            .copied()
+            .find(|path| get(doc.as_item(), path).is_some_and(Item::is_str))
+    }
+
+    /// The path of the version string in the document, following `version.workspace = true` when
+    /// the manifest is the workspace root itself.
+    pub fn resolved_version_path(
+        &self,
+        doc: &DocumentMut,
+    ) -> Result<&'static [&'static str], VersionError> {
+        if self.inherits_version()? {
+            return if doc.contains_key("workspace") {
+                Ok(WORKSPACE_VERSION)
+            } else {
+                Err(invalid(
+                    &self.path,
+                    "the version is inherited from the workspace",
+                ))
+            };
+        }
+        self.version_path(doc)
+            .ok_or_else(|| invalid(&self.path, "no version field"))
+    }
+
+    /// Replace the contents of the manifest with an edited TOML document.
+    pub fn set_toml(&mut self, doc: &DocumentMut) {
+        self.contents = doc.to_string();
+    }
+
+    /// Fail if the version of a Python project is computed by the build backend.
+    fn check_dynamic(&self, doc: &DocumentMut) -> Result<(), VersionError> {
+        let dynamic = get(doc.as_item(), &["project", "dynamic"])
+            .and_then(Item::as_array)
+            .is_some_and(|fields| fields.iter().any(|f| f.as_str() == Some("version")));
+        if dynamic {
+            Err(invalid(&self.path, "the version is dynamic"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn invalid(path: &Path, reason: &str) -> VersionError {
+    VersionError::InvalidManifest(path.display().to_string(), reason.to_owned())
+}
+
+/// Replace a string value, keeping the whitespace and comments around it.
+pub fn replace_str(value: &mut toml_edit::Value, new: &str) {
+    let decor = value.decor().clone();
+    *value = new.into();
+    *value.decor_mut() = decor;
+}
+
+pub fn get<'a>(item: &'a Item, path: &[&str]) -> Option<&'a Item> {
+    path.iter()
+        .try_fold(item, |item, key| item.as_table_like()?.get(key))
+}
+
+pub fn get_mut<'a>(item: &'a mut Item, path: &[&str]) -> Option<&'a mut Item> {
+    path.iter()
+        .try_fold(item, |item, key| item.as_table_like_mut()?.get_mut(key))
+}
+
+/// Find the contents of the top-level `"version"` string of a JSON document, without its quotes.
+fn json_version_span(json: &str) -> Option<Range<usize>> {
+    let bytes = json.as_bytes();
+    let mut i = skip_whitespace(bytes, 0);
+    if bytes.get(i) != Some(&b'{') {
+        return None;
+    }
+    i += 1;
+    loop {
+        i = skip_whitespace(bytes, i);
+        let key = string_span(bytes, i)?;
+        i = skip_whitespace(bytes, key.end + 1);
+        if bytes.get(i) != Some(&b':') {
+            return None;
+        }
+        i = skip_whitespace(bytes, i + 1);
+        if &json[key] == "version" && bytes.get(i) == Some(&b'"') {
+            return string_span(bytes, i);
+        }
+        i = skip_whitespace(bytes, skip_value(bytes, i)?);
+        match bytes.get(i) {
+            Some(b',') => i += 1,
+            _ => return None,
+        }
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+        i += 1;
+    }
+    i
+}
+
+/// The contents of the string starting at `start`, without its quotes.
+fn string_span(bytes: &[u8], start: usize) -> Option<Range<usize>> {
+    if bytes.get(start) != Some(&b'"') {
+        return None;
+    }
+    let mut i = start + 1;
+    loop {
+        match bytes.get(i)? {
+            b'\\' => i += 2,
+            b'"' => return Some(start + 1..i),
+            _ => i += 1,
+        }
+    }
+}
+
+/// Skip the value starting at `start`, returning the index following it.
+fn skip_value(bytes: &[u8], start: usize) -> Option<usize> {
+    match bytes.get(start)? {
+        b'"' => Some(string_span(bytes, start)?.end + 1),
+        b'{' | b'[' => {
+            let mut depth = 0usize;
+            let mut i = start;
+            loop {
+                match bytes.get(i)? {
+                    b'"' => {
+                        i = string_span(bytes, i)?.end + 1;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+        }
+        // Numbers, booleans and null run until the next separator
+        _ => {
+            let mut i = start;
+            while bytes
+                .get(i)
+                .is_some_and(|b| !b.is_ascii_whitespace() && !b",}]".contains(b))
+            {
+                i += 1;
+            }
+            Some(i)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    /// Set the version of a manifest, returning its previous version and the new contents.
+    fn set_version(dir: &TempDir, name: &str, version: &str) -> (String, String) {
+        let mut manifest = Manifest::open(&dir.path().join(name))
+            .and_then(Manifest::version_source)
+            .unwrap();
+        let previous = manifest.version().unwrap();
+        manifest.set_version(version).unwrap();
+        manifest.save().unwrap();
+        (previous, fs::read_to_string(&manifest.path).unwrap())
+    }
+
+    #[test]
+    fn set_cargo_version() {
+        let dir = TempDir::new("manifest-cargo");
+        dir.write(
+            "Cargo.toml",
+            r#"# The crate
+[package]
+name = "demo"
+version   =   "0.1.0" # bumped by CI
+edition = "2024"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+"#,
+        );
+        let (previous, contents) = set_version(&dir, "Cargo.toml", "0.2.0");
+        assert_eq!(previous, "0.1.0");
+        assert_eq!(
+            contents,
+            r#"# The crate
+[package]
+name = "demo"
+version   =   "0.2.0" # bumped by CI
+edition = "2024"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+"#
+        );
+    }
+
+    #[test]
+    fn set_npm_version() {
+        let dir = TempDir::new("manifest-npm");
+        dir.write(
+            "package.json",
+            r#"{
+  "name": "demo",
+  "dependencies": { "version": "1.0.0" },
+  "version" : "1.2.3",
+  "private": true
+}
+"#,
+        );
+        let (previous, contents) = set_version(&dir, "package.json", "2.0.0");
+        assert_eq!(previous, "1.2.3");
+        assert_eq!(
+            contents,
+            r#"{
+  "name": "demo",
+  "dependencies": { "version": "1.0.0" },
+  "version" : "2.0.0",
+  "private": true
+}
+"#
+        );
+    }
+
+    #[test]
+    fn set_pyproject_version() {
+        let dir = TempDir::new("manifest-pyproject");
+        dir.write(
+            "pyproject.toml",
+            r#"[project]
+name = "demo"
+# Keep in sync with the changelog
+version = "1.0.0"
+dependencies = ["requests>=2"]
+"#,
+        );
+        let (previous, contents) = set_version(&dir, "pyproject.toml", "1.1.0");
+        assert_eq!(previous, "1.0.0");
+        assert_eq!(
+            contents,
+            r#"[project]
+name = "demo"
+# Keep in sync with the changelog
+version = "1.1.0"
+dependencies = ["requests>=2"]
+"#
+        );
+    }
+
+    #[test]
+    fn set_inherited_version_in_workspace_root() {
+        let dir = TempDir::new("manifest-workspace");
+        dir.write(
+            "Cargo.toml",
+            r#"[workspace]
+members = ["crates/*"]
+
+[workspace.package]
+version = "0.3.0" # shared
+
+[package]
+name = "root"
+version.workspace = true
+"#,
+        );
+        let (previous, contents) = set_version(&dir, "Cargo.toml", "0.4.0");
+        assert_eq!(previous, "0.3.0");
+        assert_eq!(
+            contents,
+            r#"[workspace]
+members = ["crates/*"]
+
+[workspace.package]
+version = "0.4.0" # shared
+
+[package]
+name = "root"
+version.workspace = true
+"#
+        );
+    }
+
+    #[test]
+    fn set_inherited_version_from_member() {
+        let dir = TempDir::new("manifest-member");
+        dir.write(
+            "Cargo.toml",
+            r#"[workspace]
+members = ["member"]
+
+[workspace.package]
+version = "1.0.0"
+"#,
+        );
+        let member = dir.write(
+            "member/Cargo.toml",
+            r#"[package]
+name = "member"
+version.workspace = true
+"#,
+        );
+        let (previous, contents) = set_version(&dir, "member/Cargo.toml", "1.0.1");
+        assert_eq!(previous, "1.0.0");
+        assert_eq!(
+            contents,
+            r#"[workspace]
+members = ["member"]
+
+[workspace.package]
+version = "1.0.1"
+"#
+        );
+        assert_eq!(
+            fs::read_to_string(member).unwrap(),
+            r#"[package]
+name = "member"
+version.workspace = true
+"#
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::version::VersionError;
+
+/// Well-known qualifiers, from the lowest to the highest. The empty qualifier is a release, and
+/// unknown qualifiers sort after all of them, alphabetically.
+const QUALIFIERS: [&str; 7] = ["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+/// A Maven (or Gradle) version, e.g. `1.0-SNAPSHOT` or `2.3.RELEASE`, ordered like Maven's
+/// `ComparableVersion`.
+#[derive(Debug, Clone)]
+pub struct MavenVersion {
+    original: String,
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Clone)]
+enum Item {
+    /// A number, without leading zeros.
+    Int(String),
+    /// A qualifier, with its aliases resolved (e.g. `final` is the empty qualifier).
+    Str(String),
+    /// The items following a `-`, or a change between digits and letters.
+    List(Vec<Item>),
+}
+
+impl MavenVersion {
+    pub fn parse(input: &str) -> Result<Self, VersionError> {
+        let invalid =
+            |reason: &str| VersionError::InvalidMavenVersion(input.to_owned(), reason.to_owned());
+
+        let version = input.trim().to_lowercase();
+        if version.is_empty() {
+            return Err(invalid("the version is empty"));
+        }
+        if version.contains(char::is_whitespace) {
+            return Err(invalid("the version contains whitespace"));
+        }
+
+        // Each new sub-list is the last item of the list below it on the stack
+        let mut stack: Vec<Vec<Item>> = vec![vec![]];
+        let mut is_digit = false;
+        let mut start = 0;
+        for (i, c) in version.char_indices() {
+            let list = stack.last_mut().expect("the stack is never empty");
+            match c {
+                '.' | '-' => {
+                    list.push(if i == start {
+                        Item::Int("0".to_owned())
+                    } else {
+                        Item::parse(is_digit, &version[start..i])
+                    });
+                    start = i + 1;
+                    if c == '-' {
+                        stack.push(vec![]);
+                    }
+                }
+                c if c.is_ascii_digit() => {
+                    if !is_digit && i > start {
+                        list.push(Item::qualifier(&version[start..i], true));
+                        start = i;
+                        stack.push(vec![]);
+                    }
+                    is_digit = true;
+                }
+                _ => {
+                    if is_digit && i > start {
+                        list.push(Item::parse(true, &version[start..i]));
+                        start = i;
+                        stack.push(vec![]);
+                    }
+                    is_digit = false;
+                }
+            }
+        }
+        if version.len() > start {
+            let list = stack.last_mut().expect("the stack is never empty");
+            list.push(Item::parse(is_digit, &version[start..]));
+        }
+
+        let mut items = normalize(stack.pop().expect("the stack is never empty"));
+        while let Some(mut parent) = stack.pop() {
+            parent.push(Item::List(items));
+            items = normalize(parent);
+        }
+
+        Ok(Self {
+            original: input.trim().to_owned(),
+            items,
+        })
+    }
+}
+
+impl Item {
+    fn parse(is_digit: bool, s: &str) -> Self {
+        if is_digit {
+            let digits = s.trim_start_matches('0');
+            Item::Int(if digits.is_empty() { "0" } else { digits }.to_owned())
+        } else {
+            Item::qualifier(s, false)
+        }
+    }
+
+    fn qualifier(s: &str, followed_by_digit: bool) -> Self {
+        let s = match s {
+            "a" if followed_by_digit => "alpha",
+            "b" if followed_by_digit => "beta",
+            "m" if followed_by_digit => "milestone",
+            "ga" | "final" | "release" => "",
+            "cr" => "rc",
+            s => s,
+        };
+        Item::Str(s.to_owned())
+    }
+
+    /// Whether the item is equivalent to nothing, i.e. `0`, a release qualifier or an empty list.
+    fn is_null(&self) -> bool {
+        match self {
+            Item::Int(n) => n == "0",
+            Item::Str(s) => s.is_empty(),
+            Item::List(items) => items.is_empty(),
+        }
+    }
+
+    /// Compare the item to another one, or to a missing item.
+    fn compare(&self, other: Option<&Item>) -> Ordering {
+        match (self, other) {
+            (Item::Int(a), None) => {
+                if a == "0" {
+                    Ordering::Equal
+                } else {
+                    Ordering::Greater
+                }
+            }
+            (Item::Int(a), Some(Item::Int(b))) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+            (Item::Int(_), Some(_)) => Ordering::Greater,
+            (Item::Str(a), None) => qualifier_rank(a).cmp(&qualifier_rank("")),
+            (Item::Str(a), Some(Item::Str(b))) => qualifier_rank(a).cmp(&qualifier_rank(b)),
+            (Item::Str(_), Some(_)) => Ordering::Less,
+            (Item::List(a), None) => a
+                .iter()
+                .map(|item| item.compare(None))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal),
+            (Item::List(a), Some(Item::List(b))) => compare_lists(a, b),
+            (Item::List(_), Some(Item::Int(_))) => Ordering::Less,
+            (Item::List(_), Some(Item::Str(_))) => Ordering::Greater,
+        }
+    }
+}
+
+/// Remove the trailing null items of a list, looking through trailing sub-lists.
+fn normalize(mut items: Vec<Item>) -> Vec<Item> {
+    for i in (0..items.len()).rev() {
+        if items[i].is_null() {
+            items.remove(i);
+        } else if !matches!(items[i], Item::List(_)) {
+            break;
+        }
+    }
+    items
+}
+
+fn qualifier_rank(qualifier: &str) -> (usize, &str) {
+    match QUALIFIERS.iter().position(|q| *q == qualifier) {
+        Some(index) => (index, ""),
+        None => (QUALIFIERS.len(), qualifier),
+    }
+}
+
+fn compare_lists(a: &[Item], b: &[Item]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(i), b.get(i)) {
+            (Some(a), b) => a.compare(b),
+            (None, Some(b)) => b.compare(None).reverse(),
+            (None, None) => Ordering::Equal,
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+impl Ord for MavenVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_lists(&self.items, &other.items)
+    }
+}
+
+impl PartialOrd for MavenVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for MavenVersion {}
+
+impl fmt::Display for MavenVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.original)
+    }
+}
+
+/// A Maven version range, e.g. `[1.0,2.0)` or `(,1.0],[1.2,)`, made of one or more restrictions.
+/// A bare version such as `1.0` is a soft requirement, matching that version or any later one.
+#[derive(Debug, Clone)]
+pub struct MavenRange {
+    restrictions: Vec<Restriction>,
+}
+
+/// A bound of a restriction, and whether it is inclusive.
+type Bound = Option<(MavenVersion, bool)>;
+
+#[derive(Debug, Clone)]
+struct Restriction {
+    lower: Bound,
+    upper: Bound,
+}
+
+impl MavenRange {
+    pub fn parse(input: &str) -> Result<Self, VersionError> {
+        let invalid =
+            |reason: &str| VersionError::InvalidMavenRange(input.to_owned(), reason.to_owned());
+        let version = |s: &str| MavenVersion::parse(s).map_err(|e| invalid(&e.to_string()));
+
+        let mut rest = input.trim();
+        if rest.is_empty() {
+            return Err(invalid("the range is empty"));
+        }
+        if !rest.starts_with(['[', '(']) {
+            return Ok(Self {
+                restrictions: vec![Restriction {
+                    lower: Some((version(rest)?, true)),
+                    upper: None,
+                }],
+            });
+        }
+
+        let mut restrictions = vec![];
+        while !rest.is_empty() {
+            let end = rest
+                .find([']', ')'])
+                .ok_or_else(|| invalid("unbounded range"))?;
+            let (restriction, tail) = rest.split_at(end + 1);
+            let lower_inclusive = match restriction.chars().next() {
+                Some('[') => true,
+                Some('(') => false,
+                _ => return Err(invalid("ranges should start with '[' or '('")),
+            };
+            let upper_inclusive = restriction.ends_with(']');
+            let inner = &restriction[1..restriction.len() - 1];
+
+            restrictions.push(match inner.split_once(',') {
+                None if lower_inclusive && upper_inclusive => {
+                    let exact = version(inner.trim())?;
+                    Restriction {
+                        lower: Some((exact.clone(), true)),
+                        upper: Some((exact, true)),
+                    }
+                }
+                None => return Err(invalid("a single version should be surrounded by []")),
+                Some((lower, upper)) => {
+                    let bound = |s: &str, inclusive| -> Result<_, VersionError> {
+                        match s.trim() {
+                            "" => Ok(None),
+                            s => Ok(Some((version(s)?, inclusive))),
+                        }
+                    };
+                    let restriction = Restriction {
+                        lower: bound(lower, lower_inclusive)?,
+                        upper: bound(upper, upper_inclusive)?,
+                    };
+                    if let (Some((lower, _)), Some((upper, _))) =
+                        (&restriction.lower, &restriction.upper)
+                        && upper < lower
+                    {
+                        return Err(invalid("the upper bound is lower than the lower bound"));
+                    }
+                    restriction
+                }
+            });
+
+            rest = tail.trim_start();
+            if let Some(tail) = rest.strip_prefix(',') {
+                rest = tail.trim_start();
+                if rest.is_empty() {
+                    return Err(invalid("trailing ','"));
+                }
+            }
+        }
+        Ok(Self { restrictions })
+    }
+
+    pub fn matches(&self, version: &MavenVersion) -> bool {
+        self.restrictions.iter().any(|r| r.contains(version))
+    }
+}
+
+impl Restriction {
+    fn contains(&self, version: &MavenVersion) -> bool {
+        let above = self
+            .lower
+            .as_ref()
+            .is_none_or(|(lower, inclusive)| version > lower || (*inclusive && version == lower));
+        let below = self
+            .upper
+            .as_ref()
+            .is_none_or(|(upper, inclusive)| version < upper || (*inclusive && version == upper));
+        above && below
+    }
+}
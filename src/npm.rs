@@ -0,0 +1,154 @@
+use semver::{Comparator, Op, Prerelease, VersionReq};
+
+use crate::version::VersionError;
+
+/// A possibly partial version, where `None` stands for a wildcard (`x`, `X` or `*`) or a missing
+/// component.
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Prerelease,
+}
+
+impl Partial {
+    fn parse(input: &str) -> Result<Self, VersionError> {
+        let invalid = || VersionError::InvalidNpmRange(input.to_owned());
+
+        let input = input.trim_start_matches(['v', '=']);
+        // Build metadata is ignored by npm ranges
+        let input = input.split_once('+').map_or(input, |(core, _)| core);
+        let (core, pre) = match input.split_once('-') {
+            Some((core, pre)) => (core, Prerelease::new(pre).map_err(|_| invalid())?),
+            None => (input, Prerelease::EMPTY),
+        };
+
+        let mut components = [None; 3];
+        let mut wildcard = false;
+        for (i, part) in core.split('.').enumerate() {
+            if i >= 3 || part.is_empty() {
+                return Err(invalid());
+            }
+            if matches!(part, "x" | "X" | "*") {
+                wildcard = true;
+            } else if !wildcard {
+                // Anything after a wildcard is a wildcard as well, e.g. 1.x.3 is 1.x.x
+                components[i] = Some(part.parse::<u64>().map_err(|_| invalid())?);
+            }
+        }
+
+        let [major, minor, patch] = components;
+        if !pre.is_empty() && patch.is_none() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    fn comparator(&self, op: Op) -> Option<Comparator> {
+        Some(Comparator {
+            op,
+            major: self.major?,
+            minor: self.minor,
+            patch: self.minor.and(self.patch),
+            pre: self.pre.clone(),
+        })
+    }
+}
+
+/// Parse an npm (node-semver) range, such as `1.2.3 - 2.3.4`, `>=1.0.0 <2.0.0 || ^3`, `1.x` or
+/// `latest`, into equivalent Cargo requirements, one per `||` alternative.
+pub fn parse_range(input: &str) -> Result<Vec<VersionReq>, VersionError> {
+    input.split("||").map(parse_alternative).collect()
+}
+
+fn parse_alternative(input: &str) -> Result<VersionReq, VersionError> {
+    let input = input.trim();
+    if input.is_empty() || input == "latest" {
+        return Ok(VersionReq::STAR);
+    }
+
+    // Hyphen ranges: `1.2 - 2.3.4` is `>=1.2.0 <=2.3.4`
+    if let Some((from, to)) = input.split_once(" - ") {
+        let from = Partial::parse(from.trim())?;
+        let to = Partial::parse(to.trim())?;
+        let comparators = [from.comparator(Op::GreaterEq), to.comparator(Op::LessEq)]
+            .into_iter()
+            .flatten()
+            .collect();
+        return Ok(VersionReq { comparators });
+    }
+
+    let mut comparators = vec![];
+    for token in tokens(input) {
+        let (op, version) = split_op(&token);
+        let partial = Partial::parse(version)?;
+        let comparator = match op {
+            // A bare version is an x-range, e.g. `1.2` matches any 1.2.x version
+            None if partial.patch.is_none() => partial.comparator(Op::Wildcard),
+            None | Some("=") => partial.comparator(Op::Exact),
+            Some(">") => partial.comparator(Op::Greater),
+            Some(">=") => partial.comparator(Op::GreaterEq),
+            Some("<") => partial.comparator(Op::Less),
+            Some("<=") => partial.comparator(Op::LessEq),
+            Some("~" | "~>") => partial.comparator(Op::Tilde),
+            Some("^") => partial.comparator(Op::Caret),
+            Some(_) => return Err(VersionError::InvalidNpmRange(input.to_owned())),
+        };
+        match (comparator, op) {
+            (Some(comparator), _) => comparators.push(comparator),
+            // `<*` and `>*` can't match anything
+            (None, Some("<" | ">")) => {
+                return Ok(VersionReq {
+                    comparators: vec![Comparator {
+                        op: Op::Less,
+                        major: 0,
+                        minor: Some(0),
+                        patch: Some(0),
+                        pre: Prerelease::new("0")?,
+                    }],
+                });
+            }
+            // Any other wildcard matches everything
+            (None, _) => {}
+        }
+    }
+    Ok(VersionReq { comparators })
+}
+
+/// Split a range into whitespace-separated comparators, allowing whitespace between an operator
+/// and its version (e.g. `>= 1.2.3`).
+fn tokens(input: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = vec![];
+    let mut pending_op = String::new();
+    for word in input.split_whitespace() {
+        if word
+            .chars()
+            .all(|c| matches!(c, '<' | '>' | '=' | '~' | '^'))
+        {
+            pending_op.push_str(word);
+        } else {
+            tokens.push(format!("{pending_op}{word}"));
+            pending_op.clear();
+        }
+    }
+    if !pending_op.is_empty() {
+        tokens.push(pending_op);
+    }
+    tokens
+}
+
+fn split_op(token: &str) -> (Option<&str>, &str) {
+    let len = token
+        .find(|c: char| !matches!(c, '<' | '>' | '=' | '~' | '^'))
+        .unwrap_or(token.len());
+    if len == 0 {
+        (None, token)
+    } else {
+        (Some(&token[..len]), &token[len..])
+    }
+}
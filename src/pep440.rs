@@ -0,0 +1,527 @@
+use std::{cmp::Ordering, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::version::{ALPHA, BETA, RC, VersionError};
+
+/// The kind of a PEP 440 pre-release, in increasing order.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, strum::Display,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum PreKind {
+    A,
+    B,
+    Rc,
+}
+
+/// A segment of a local version label. Alphanumeric segments sort before numeric ones.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LocalSegment {
+    Str(String),
+    Num(u64),
+}
+
+impl fmt::Display for LocalSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocalSegment::Str(s) => f.write_str(s),
+            LocalSegment::Num(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+/// A Python package version, as specified by PEP 440, e.g. `1!2.0.3rc1.post2.dev3+local.7`.
+///
+/// Versions are kept in their normalized form, so `1.0-ALPHA.1` is stored (and displayed) as
+/// `1.0a1`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pep440Version {
+    pub epoch: u64,
+    pub release: Vec<u64>,
+    pub pre: Option<(PreKind, u64)>,
+    pub post: Option<u64>,
+    pub dev: Option<u64>,
+    pub local: Vec<LocalSegment>,
+}
+
+impl Pep440Version {
+    pub fn parse(input: &str) -> Result<Self, VersionError> {
+        let invalid = || VersionError::InvalidPep440Version(input.to_owned());
+
+        let normalized = input.trim().to_lowercase();
+        let s = normalized.strip_prefix('v').unwrap_or(&normalized);
+        let (public, local) = match s.split_once('+') {
+            Some((public, local)) => (public, Some(local)),
+            None => (s, None),
+        };
+        let (epoch, rest) = match public.split_once('!') {
+            Some((epoch, rest)) => (epoch.parse().map_err(|_| invalid())?, rest),
+            None => (0, public),
+        };
+
+        let (first, mut rest) = number(rest).ok_or_else(invalid)?;
+        let mut release = vec![first];
+        while let Some((n, tail)) = rest.strip_prefix('.').and_then(number) {
+            release.push(n);
+            rest = tail;
+        }
+
+        let mut pre = None;
+        if let Some((label, tail)) = label(
+            rest,
+            &["alpha", "a", "beta", "b", "preview", "pre", "rc", "c"],
+        ) {
+            let kind = match label {
+                "alpha" | "a" => PreKind::A,
+                "beta" | "b" => PreKind::B,
+                _ => PreKind::Rc,
+            };
+            let (n, tail) = separated_number(tail).unwrap_or((0, tail));
+            pre = Some((kind, n));
+            rest = tail;
+        }
+
+        let mut post = None;
+        if let Some((n, tail)) = rest.strip_prefix('-').and_then(number) {
+            // Implicit post-release, e.g. `1.0-1`
+            post = Some(n);
+            rest = tail;
+        } else if let Some((_, tail)) = label(rest, &["post", "rev", "r"]) {
+            let (n, tail) = separated_number(tail).unwrap_or((0, tail));
+            post = Some(n);
+            rest = tail;
+        }
+
+        let mut dev = None;
+        if let Some((_, tail)) = label(rest, &["dev"]) {
+            let (n, tail) = separated_number(tail).unwrap_or((0, tail));
+            dev = Some(n);
+            rest = tail;
+        }
+
+        if !rest.is_empty() {
+            return Err(invalid());
+        }
+
+        let local = match local {
+            Some(local) => local
+                .split(['.', '-', '_'])
+                .map(|segment| {
+                    if segment.is_empty() || !segment.chars().all(|c| c.is_ascii_alphanumeric()) {
+                        Err(invalid())
+                    } else if let Ok(n) = segment.parse() {
+                        Ok(LocalSegment::Num(n))
+                    } else {
+                        Ok(LocalSegment::Str(segment.to_owned()))
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
+
+        Ok(Self {
+            epoch,
+            release,
+            pre,
+            post,
+            dev,
+            local,
+        })
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    pub fn is_postrelease(&self) -> bool {
+        self.post.is_some()
+    }
+
+    /// The version without its local label.
+    pub fn public(&self) -> Self {
+        Self {
+            local: vec![],
+            ..self.clone()
+        }
+    }
+
+    /// The epoch and release segments only, e.g. `1.2.3` for `1.2.3rc1.post1`.
+    pub fn base(&self) -> Self {
+        Self {
+            epoch: self.epoch,
+            release: self.release.clone(),
+            pre: None,
+            post: None,
+            dev: None,
+            local: vec![],
+        }
+    }
+
+    /// Convert into the equivalent SemVer version. Only versions without an epoch, post-release or
+    /// dev-release, with at most 3 release segments, can be converted. Pre-releases are mapped to
+    /// `alpha.N`, `beta.N` and `rc.N`, and the local label becomes the build metadata.
+    pub fn to_semver(&self) -> Result<semver::Version, VersionError> {
+        let not_convertible =
+            |reason: &str| VersionError::NoSemverEquivalent(self.to_string(), reason.to_owned());
+
+        if self.epoch != 0 {
+            return Err(not_convertible("SemVer has no epochs"));
+        }
+        if self.release.len() > 3 {
+            return Err(not_convertible("SemVer versions have only 3 components"));
+        }
+        if self.post.is_some() {
+            return Err(not_convertible("SemVer has no post-releases"));
+        }
+        if self.dev.is_some() {
+            return Err(not_convertible("SemVer has no dev-releases"));
+        }
+
+        let component = |i: usize| self.release.get(i).copied().unwrap_or(0);
+        let mut version = semver::Version::new(component(0), component(1), component(2));
+        if let Some((kind, n)) = self.pre {
+            let label = match kind {
+                PreKind::A => ALPHA,
+                PreKind::B => BETA,
+                PreKind::Rc => RC,
+            };
+            version.pre = semver::Prerelease::new(&format!("{label}.{n}"))?;
+        }
+        if !self.local.is_empty() {
+            version.build = semver::BuildMetadata::new(&self.local_label())?;
+        }
+        Ok(version)
+    }
+
+    /// Convert a SemVer version into the equivalent PEP 440 version. The pre-release, if any, has
+    /// to be one of `alpha.N`, `beta.N` or `rc.N`, and the build metadata becomes the local label.
+    pub fn from_semver(version: &semver::Version) -> Result<Self, VersionError> {
+        let not_convertible =
+            |reason: &str| VersionError::NoPep440Equivalent(version.to_string(), reason.to_owned());
+
+        let pre = if version.pre.is_empty() {
+            None
+        } else {
+            let malformed = || not_convertible("the pre-release should look like 'rc.1'");
+            let (label, n) = version.pre.split_once('.').ok_or_else(malformed)?;
+            // Leading zeros wouldn't round-trip
+            let n = n
+                .parse::<u64>()
+                .ok()
+                .filter(|num| num.to_string() == n)
+                .ok_or_else(malformed)?;
+            let kind = match label {
+                ALPHA => PreKind::A,
+                BETA => PreKind::B,
+                RC => PreKind::Rc,
+                _ => {
+                    return Err(not_convertible(
+                        "the pre-release label should be 'alpha', 'beta' or 'rc'",
+                    ));
+                }
+            };
+            Some((kind, n))
+        };
+
+        let local = if version.build.is_empty() {
+            vec![]
+        } else {
+            let local = Self::parse(&format!("0+{}", version.build))
+                .map(|v| v.local)
+                .map_err(|_| not_convertible("the build metadata is not a valid local label"))?;
+            // Separators and leading zeros are normalized by PEP 440, and wouldn't round-trip
+            if local
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(".")
+                != version.build.as_str()
+            {
+                return Err(not_convertible(
+                    "the build metadata is not a normalized local label",
+                ));
+            }
+            local
+        };
+
+        Ok(Self {
+            epoch: 0,
+            release: vec![version.major, version.minor, version.patch],
+            pre,
+            post: None,
+            dev: None,
+            local,
+        })
+    }
+
+    fn local_label(&self) -> String {
+        self.local
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Whether the release segment starts with `prefix`, padding it with zeros if needed.
+    fn has_release_prefix(&self, epoch: u64, prefix: &[u64]) -> bool {
+        self.epoch == epoch
+            && prefix
+                .iter()
+                .enumerate()
+                .all(|(i, n)| self.release.get(i).copied().unwrap_or(0) == *n)
+    }
+}
+
+/// The position of the pre-release part in the ordering: dev-releases of a final release come
+/// before its pre-releases, which come before the final release.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum PreKey {
+    DevOnly,
+    Pre(PreKind, u64),
+    Final,
+}
+
+impl Pep440Version {
+    fn pre_key(&self) -> PreKey {
+        match (self.pre, self.post, self.dev) {
+            (Some((kind, n)), _, _) => PreKey::Pre(kind, n),
+            (None, None, Some(_)) => PreKey::DevOnly,
+            _ => PreKey::Final,
+        }
+    }
+}
+
+impl Ord for Pep440Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.release.len().max(other.release.len());
+        let release = |v: &Self, i: usize| v.release.get(i).copied().unwrap_or(0);
+
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| {
+                (0..len)
+                    .map(|i| release(self, i).cmp(&release(other, i)))
+                    .find(|o| o.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            .then_with(|| self.post.cmp(&other.post))
+            // Versions without a dev-release come after the ones with one
+            .then_with(|| (self.dev.is_none(), self.dev).cmp(&(other.dev.is_none(), other.dev)))
+            .then_with(|| self.local.cmp(&other.local))
+    }
+}
+
+impl PartialOrd for Pep440Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Pep440Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Pep440Version {}
+
+impl fmt::Display for Pep440Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+        let release: Vec<String> = self.release.iter().map(ToString::to_string).collect();
+        f.write_str(&release.join("."))?;
+        if let Some((kind, n)) = self.pre {
+            write!(f, "{kind}{n}")?;
+        }
+        if let Some(n) = self.post {
+            write!(f, ".post{n}")?;
+        }
+        if let Some(n) = self.dev {
+            write!(f, ".dev{n}")?;
+        }
+        if !self.local.is_empty() {
+            write!(f, "+{}", self.local_label())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a number at the start of `s`, returning it along with the rest of the string.
+fn number(s: &str) -> Option<(u64, &str)> {
+    let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let n = s[..len].parse().ok()?;
+    Some((n, &s[len..]))
+}
+
+/// Parse a number optionally preceded by a separator, e.g. the `.1` of `rc.1`.
+fn separated_number(s: &str) -> Option<(u64, &str)> {
+    number(s.strip_prefix(['.', '-', '_']).unwrap_or(s))
+}
+
+/// Parse one of the given labels, optionally preceded by a separator.
+fn label<'a>(s: &'a str, labels: &[&'static str]) -> Option<(&'static str, &'a str)> {
+    let s = s.strip_prefix(['.', '-', '_']).unwrap_or(s);
+    labels
+        .iter()
+        .find_map(|label| s.strip_prefix(label).map(|rest| (*label, rest)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Compatible,
+    Equal,
+    NotEqual,
+    LessEq,
+    GreaterEq,
+    Less,
+    Greater,
+    Arbitrary,
+}
+
+/// A single version specifier, e.g. `~=1.4.2` or `!=1.2.*`.
+#[derive(Debug, Clone)]
+struct Specifier {
+    op: Operator,
+    version: Pep440Version,
+    /// Whether the version ends with `.*`, for prefix matching.
+    wildcard: bool,
+    /// The version as written, for arbitrary equality (`===`).
+    raw: String,
+}
+
+impl Specifier {
+    fn parse(input: &str) -> Result<Self, VersionError> {
+        let invalid = |reason: &str| {
+            VersionError::InvalidPep440Specifier(input.to_owned(), reason.to_owned())
+        };
+
+        let (op, raw) = [
+            ("~=", Operator::Compatible),
+            ("===", Operator::Arbitrary),
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("<=", Operator::LessEq),
+            (">=", Operator::GreaterEq),
+            ("<", Operator::Less),
+            (">", Operator::Greater),
+        ]
+        .into_iter()
+        .find_map(|(symbol, op)| input.strip_prefix(symbol).map(|rest| (op, rest.trim())))
+        .ok_or_else(|| invalid("missing operator"))?;
+
+        if op == Operator::Arbitrary {
+            // Arbitrary equality compares strings, the version is only a placeholder
+            return Ok(Self {
+                op,
+                version: Pep440Version::parse("0")?,
+                wildcard: false,
+                raw: raw.to_owned(),
+            });
+        }
+
+        let (version, wildcard) = match raw.strip_suffix(".*") {
+            Some(prefix) => (prefix, true),
+            None => (raw, false),
+        };
+        let version = Pep440Version::parse(version)?;
+
+        if wildcard {
+            if !matches!(op, Operator::Equal | Operator::NotEqual) {
+                return Err(invalid("wildcards are only allowed with == and !="));
+            }
+            if version.base() != version || !version.local.is_empty() {
+                return Err(invalid(
+                    "wildcards are only allowed after the release segment",
+                ));
+            }
+        }
+        if !version.local.is_empty() && !matches!(op, Operator::Equal | Operator::NotEqual) {
+            return Err(invalid("local versions are only allowed with == and !="));
+        }
+        if op == Operator::Compatible && version.release.len() < 2 {
+            return Err(invalid("~= needs at least 2 release segments"));
+        }
+
+        Ok(Self {
+            op,
+            version,
+            wildcard,
+            raw: raw.to_owned(),
+        })
+    }
+
+    /// Check whether a version matches the specifier. Arbitrary equality compares the version as
+    /// written in `raw`, instead of its normalized form.
+    fn matches(&self, candidate: &Pep440Version, raw: &str) -> bool {
+        let spec = &self.version;
+        let public = candidate.public();
+        let same_base = || candidate.base() == spec.base();
+
+        match self.op {
+            Operator::Equal | Operator::NotEqual => {
+                let equal = if self.wildcard {
+                    candidate.has_release_prefix(spec.epoch, &spec.release)
+                } else if spec.local.is_empty() {
+                    public == *spec
+                } else {
+                    candidate == spec
+                };
+                equal == (self.op == Operator::Equal)
+            }
+            Operator::Compatible => {
+                let prefix = &spec.release[..spec.release.len() - 1];
+                public >= *spec && candidate.has_release_prefix(spec.epoch, prefix)
+            }
+            Operator::LessEq => public <= *spec,
+            Operator::GreaterEq => public >= *spec,
+            // `<1.2` doesn't match the pre-releases of 1.2, unless it's a pre-release itself
+            Operator::Less => {
+                public < *spec
+                    && !(!spec.is_prerelease() && candidate.is_prerelease() && same_base())
+            }
+            // `>1.2` doesn't match the post-releases and local versions of 1.2
+            Operator::Greater => {
+                public > *spec
+                    && !(!spec.is_postrelease() && candidate.is_postrelease() && same_base())
+                    && (candidate.local.is_empty() || !same_base())
+            }
+            Operator::Arbitrary => raw.trim().eq_ignore_ascii_case(&self.raw),
+        }
+    }
+}
+
+/// A set of comma-separated PEP 440 version specifiers, e.g. `>=1.2, !=1.3.*, <2`. A version
+/// matches the set if it matches all of the specifiers.
+#[derive(Debug, Clone)]
+pub struct Pep440Specifiers(Vec<Specifier>);
+
+impl Pep440Specifiers {
+    pub fn parse(input: &str) -> Result<Self, VersionError> {
+        input
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(Specifier::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+
+    /// Check whether a version matches all the specifiers. `raw` is the version as written, for
+    /// arbitrary equality (`===`).
+    ///
+    /// Like pip, pre-releases and dev-releases are only matched if `include_prerelease` is set, or
+    /// if one of the specifiers explicitly mentions a pre-release.
+    pub fn matches(&self, version: &Pep440Version, raw: &str, include_prerelease: bool) -> bool {
+        if version.is_prerelease()
+            && !include_prerelease
+            && !self.0.iter().any(|s| s.version.is_prerelease())
+        {
+            return false;
+        }
+        self.0.iter().all(|s| s.matches(version, raw))
+    }
+}
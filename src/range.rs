@@ -0,0 +1,276 @@
+use std::{
+    cmp::{self, Ordering},
+    ops::Bound,
+};
+
+use semver::{Comparator, Op, Prerelease, Version, VersionReq};
+
+/// A contiguous range of versions, using the total SemVer ordering (i.e. pre-releases are
+/// ordered like any other version).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    pub lower: Bound<Version>,
+    pub upper: Bound<Version>,
+}
+
+impl VersionRange {
+    pub const FULL: VersionRange = VersionRange {
+        lower: Bound::Unbounded,
+        upper: Bound::Unbounded,
+    };
+
+    /// Build the range of versions matched by a single comparator.
+    ///
+    /// Partial versions follow the same conventions as npm: `<1.2` excludes the pre-releases of
+    /// `1.2.0` (`<1.2.0-0`), while the upper bound of `^1.2.3` is `<2.0.0-0`.
+    pub fn from_comparator(cmp: &Comparator) -> Self {
+        let exact = || Version {
+            major: cmp.major,
+            minor: cmp.minor.unwrap_or(0),
+            patch: cmp.patch.unwrap_or(0),
+            pre: cmp.pre.clone(),
+            build: Default::default(),
+        };
+        // The first version that is no longer matched by the partial version, e.g. 1.3.0-0 for 1.2
+        let after_partial = || match (cmp.minor, cmp.patch) {
+            (None, _) => lowest(cmp.major + 1, 0, 0),
+            (Some(minor), None) => lowest(cmp.major, minor + 1, 0),
+            (Some(minor), Some(patch)) => lowest(cmp.major, minor, patch + 1),
+        };
+        let is_partial = cmp.patch.is_none();
+
+        let (lower, upper) = match cmp.op {
+            Op::Exact | Op::Wildcard if is_partial => {
+                (Bound::Included(exact()), Bound::Excluded(after_partial()))
+            }
+            Op::Exact | Op::Wildcard => (Bound::Included(exact()), Bound::Included(exact())),
+            Op::Greater if is_partial => {
+                let next = Version {
+                    pre: Prerelease::EMPTY,
+                    ..after_partial()
+                };
+                (Bound::Included(next), Bound::Unbounded)
+            }
+            Op::Greater => (Bound::Excluded(exact()), Bound::Unbounded),
+            Op::GreaterEq => (Bound::Included(exact()), Bound::Unbounded),
+            Op::Less if is_partial => (Bound::Unbounded, Bound::Excluded(lowest_of(exact()))),
+            Op::Less => (Bound::Unbounded, Bound::Excluded(exact())),
+            Op::LessEq if is_partial => (Bound::Unbounded, Bound::Excluded(after_partial())),
+            Op::LessEq => (Bound::Unbounded, Bound::Included(exact())),
+            Op::Tilde => {
+                let upper = match cmp.minor {
+                    None => lowest(cmp.major + 1, 0, 0),
+                    Some(minor) => lowest(cmp.major, minor + 1, 0),
+                };
+                (Bound::Included(exact()), Bound::Excluded(upper))
+            }
+            Op::Caret => {
+                let upper = match (cmp.major, cmp.minor, cmp.patch) {
+                    (0, None, _) => lowest(1, 0, 0),
+                    (0, Some(0), Some(patch)) => lowest(0, 0, patch + 1),
+                    (0, Some(minor), _) => lowest(0, minor + 1, 0),
+                    (major, _, _) => lowest(major + 1, 0, 0),
+                };
+                (Bound::Included(exact()), Bound::Excluded(upper))
+            }
+            _ => (Bound::Unbounded, Bound::Unbounded),
+        };
+        Self { lower, upper }
+    }
+
+    /// Build the range of versions matched by all the comparators of a requirement.
+    pub fn from_req(req: &VersionReq) -> Self {
+        req.comparators
+            .iter()
+            .map(Self::from_comparator)
+            .fold(Self::FULL, |acc, r| acc.intersect(&r))
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        let lower = cmp::max_by(&self.lower, &other.lower, |a, b| cmp_lower(a, b)).clone();
+        let upper = cmp::min_by(&self.upper, &other.upper, |a, b| cmp_upper(a, b)).clone();
+        Self { lower, upper }
+    }
+
+    /// Whether no version can fall in this range. Build metadata is not taken into account, so
+    /// e.g. `>1.2.3, <1.2.4` is empty.
+    pub fn is_empty(&self) -> bool {
+        let lower = match &self.lower {
+            // The version right after a release is the lowest pre-release of the next patch
+            Bound::Excluded(v) if v.pre.is_empty() => {
+                Bound::Included(lowest(v.major, v.minor, v.patch + 1))
+            }
+            lower => lower.clone(),
+        };
+        // Unless the lower bound opts into pre-releases, the pre-releases of the upper bound
+        // can't be matched, e.g. `>1.2.3, <1.2.4` doesn't match 1.2.4-0
+        let upper = match (&self.lower, &self.upper) {
+            (Bound::Included(l) | Bound::Excluded(l), Bound::Excluded(u))
+                if l.pre.is_empty() && u.pre.is_empty() =>
+            {
+                Bound::Excluded(lowest_of(u.clone()))
+            }
+            (_, upper) => upper.clone(),
+        };
+        match (&lower, &upper) {
+            // Nothing is below the lowest possible version
+            (_, Bound::Excluded(u)) if *u == lowest(0, 0, 0) => true,
+            (Bound::Unbounded, _) | (_, Bound::Unbounded) => false,
+            (Bound::Included(l), Bound::Included(u)) => l > u,
+            (Bound::Included(l), Bound::Excluded(u))
+            | (Bound::Excluded(l), Bound::Included(u))
+            | (Bound::Excluded(l), Bound::Excluded(u)) => l >= u,
+        }
+    }
+
+    /// Convert this range back into a requirement, e.g. `>=1.2.0, <2.0.0`.
+    ///
+    /// Upper bounds on the lowest pre-release of a version (e.g. `<2.0.0-0`) are written without
+    /// the pre-release, since pre-releases are excluded by default when matching anyway. An empty
+    /// range is written as `<0.0.0-0`, which matches no version at all.
+    pub fn to_req(&self) -> VersionReq {
+        if self.is_empty() {
+            return VersionReq {
+                comparators: vec![comparator(Op::Less, &lowest(0, 0, 0))],
+            };
+        }
+        if let (Bound::Included(l), Bound::Included(u)) = (&self.lower, &self.upper)
+            && l == u
+        {
+            return VersionReq {
+                comparators: vec![comparator(Op::Exact, l)],
+            };
+        }
+
+        let mut comparators = vec![];
+        match &self.lower {
+            Bound::Included(v) => comparators.push(comparator(Op::GreaterEq, v)),
+            Bound::Excluded(v) => comparators.push(comparator(Op::Greater, v)),
+            Bound::Unbounded => {}
+        }
+        match &self.upper {
+            Bound::Included(v) => comparators.push(comparator(Op::LessEq, v)),
+            Bound::Excluded(v) if v.pre.as_str() == "0" => comparators.push(comparator(
+                Op::Less,
+                &Version::new(v.major, v.minor, v.patch),
+            )),
+            Bound::Excluded(v) => comparators.push(comparator(Op::Less, v)),
+            Bound::Unbounded => {}
+        }
+        VersionReq { comparators }
+    }
+
+    pub fn contains(&self, version: &Version) -> bool {
+        let above_lower = match &self.lower {
+            Bound::Included(v) => version >= v,
+            Bound::Excluded(v) => version > v,
+            Bound::Unbounded => true,
+        };
+        let below_upper = match &self.upper {
+            Bound::Included(v) => version <= v,
+            Bound::Excluded(v) => version < v,
+            Bound::Unbounded => true,
+        };
+        above_lower && below_upper
+    }
+}
+
+/// Whether a version satisfies a requirement. Unless `include_prerelease` is set, this follows
+/// Cargo's rules, where pre-releases only match if the requirement explicitly opts into them.
+pub fn matches(req: &VersionReq, version: &Version, include_prerelease: bool) -> bool {
+    if include_prerelease {
+        VersionRange::from_req(req).contains(version)
+    } else {
+        req.matches(version)
+    }
+}
+
+/// The lowest possible version for the given version core, i.e. its `-0` pre-release.
+fn lowest(major: u64, minor: u64, patch: u64) -> Version {
+    lowest_of(Version::new(major, minor, patch))
+}
+
+fn lowest_of(mut version: Version) -> Version {
+    version.pre = Prerelease::new("0").expect("0 is a valid pre-release");
+    version
+}
+
+/// Compute the union of several ranges, as a sorted list of disjoint ranges. Empty ranges are
+/// dropped, and ranges that overlap or touch are merged.
+pub fn union(ranges: impl IntoIterator<Item = VersionRange>) -> Vec<VersionRange> {
+    let mut ranges: Vec<_> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+    ranges.sort_by(|a, b| cmp_lower(&a.lower, &b.lower));
+
+    let mut merged: Vec<VersionRange> = vec![];
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if touches(&last.upper, &range.lower) => {
+                if cmp_upper(&range.upper, &last.upper) == Ordering::Greater {
+                    last.upper = range.upper;
+                }
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Convert ranges, as returned by [`union`], into the alternatives of an equivalent requirement.
+/// An empty list gives `<0.0.0-0`, which matches no version at all.
+pub fn to_reqs(ranges: &[VersionRange]) -> Vec<VersionReq> {
+    if ranges.is_empty() {
+        return vec![VersionReq {
+            comparators: vec![comparator(Op::Less, &lowest(0, 0, 0))],
+        }];
+    }
+    ranges.iter().map(VersionRange::to_req).collect()
+}
+
+fn comparator(op: Op, version: &Version) -> Comparator {
+    Comparator {
+        op,
+        major: version.major,
+        minor: Some(version.minor),
+        patch: Some(version.patch),
+        pre: version.pre.clone(),
+    }
+}
+
+/// Order lower bounds from the least to the most restrictive.
+fn cmp_lower(a: &Bound<Version>, b: &Bound<Version>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => {
+            a.cmp(b)
+        }
+        (Bound::Included(a), Bound::Excluded(b)) => a.cmp(b).then(Ordering::Less),
+        (Bound::Excluded(a), Bound::Included(b)) => a.cmp(b).then(Ordering::Greater),
+    }
+}
+
+/// Order upper bounds from the most to the least restrictive.
+fn cmp_upper(a: &Bound<Version>, b: &Bound<Version>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Greater,
+        (_, Bound::Unbounded) => Ordering::Less,
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => {
+            a.cmp(b)
+        }
+        (Bound::Included(a), Bound::Excluded(b)) => a.cmp(b).then(Ordering::Greater),
+        (Bound::Excluded(a), Bound::Included(b)) => a.cmp(b).then(Ordering::Less),
+    }
+}
+
+/// Whether a range ending at `upper` overlaps or is contiguous with a range starting at `lower`.
+fn touches(upper: &Bound<Version>, lower: &Bound<Version>) -> bool {
+    match (upper, lower) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+        (Bound::Excluded(u), Bound::Excluded(l)) => l < u,
+        (Bound::Included(u), Bound::Included(l))
+        | (Bound::Included(u), Bound::Excluded(l))
+        | (Bound::Excluded(u), Bound::Included(l)) => l <= u,
+    }
+}
@@ -0,0 +1,196 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::version::VersionError;
+
+/// An RPM package version, e.g. `1:2.3.4-1.el9`, made of an optional epoch, the version and an
+/// optional release.
+#[derive(Debug, Clone)]
+pub struct RpmVersion {
+    pub epoch: Option<u64>,
+    pub version: String,
+    pub release: Option<String>,
+}
+
+impl RpmVersion {
+    pub fn parse(input: &str) -> Result<Self, VersionError> {
+        let invalid =
+            |reason: &str| VersionError::InvalidRpmVersion(input.to_owned(), reason.to_owned());
+
+        let s = input.trim();
+        let (epoch, rest) = match s.split_once(':') {
+            Some((epoch, rest)) => (
+                Some(
+                    epoch
+                        .parse()
+                        .map_err(|_| invalid("the epoch should be a number"))?,
+                ),
+                rest,
+            ),
+            None => (None, s),
+        };
+        let (version, release) = match rest.rsplit_once('-') {
+            Some((version, release)) => (version, Some(release)),
+            None => (rest, None),
+        };
+
+        if version.is_empty() {
+            return Err(invalid("the version is empty"));
+        }
+        if release.is_some_and(str::is_empty) {
+            return Err(invalid("the release is empty"));
+        }
+
+        Ok(Self {
+            epoch,
+            version: version.to_owned(),
+            release: release.map(ToOwned::to_owned),
+        })
+    }
+
+    /// Compare two versions like `rpm`: a missing epoch is 0, and releases are only compared if
+    /// both versions have one, so that e.g. `1.0` matches `1.0-1`.
+    pub fn vercmp(&self, other: &Self) -> Ordering {
+        self.cmp_epoch_version(other)
+            .then_with(|| match (&self.release, &other.release) {
+                (Some(a), Some(b)) => rpmvercmp(a, b),
+                _ => Ordering::Equal,
+            })
+    }
+
+    fn cmp_epoch_version(&self, other: &Self) -> Ordering {
+        self.epoch
+            .unwrap_or(0)
+            .cmp(&other.epoch.unwrap_or(0))
+            .then_with(|| rpmvercmp(&self.version, &other.version))
+    }
+}
+
+impl Ord for RpmVersion {
+    /// Order versions like `rpm`, except that a missing release sorts before any release.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_epoch_version(other)
+            .then_with(|| match (&self.release, &other.release) {
+                (Some(a), Some(b)) => rpmvercmp(a, b),
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            })
+    }
+}
+
+impl PartialOrd for RpmVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for RpmVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for RpmVersion {}
+
+impl fmt::Display for RpmVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(epoch) = self.epoch {
+            write!(f, "{epoch}:")?;
+        }
+        f.write_str(&self.version)?;
+        if let Some(release) = &self.release {
+            write!(f, "-{release}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare two versions or releases, like rpm's `rpmvercmp`: the strings are split into
+/// alphabetic and numeric segments, numeric segments being newer than alphabetic ones. A `~`
+/// sorts before anything, and a `^` sorts after the end of the string but before anything else.
+fn rpmvercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+    let (mut one, mut two) = (a.as_bytes(), b.as_bytes());
+    let is_separator = |c: &u8| !c.is_ascii_alphanumeric() && *c != b'~' && *c != b'^';
+
+    loop {
+        while one.first().is_some_and(is_separator) {
+            one = &one[1..];
+        }
+        while two.first().is_some_and(is_separator) {
+            two = &two[1..];
+        }
+
+        match (one.first(), two.first()) {
+            (Some(b'~'), Some(b'~')) => {
+                one = &one[1..];
+                two = &two[1..];
+                continue;
+            }
+            (Some(b'~'), _) => return Ordering::Less,
+            (_, Some(b'~')) => return Ordering::Greater,
+            (Some(b'^'), Some(b'^')) => {
+                one = &one[1..];
+                two = &two[1..];
+                continue;
+            }
+            (None, Some(b'^')) => return Ordering::Less,
+            (Some(b'^'), None) => return Ordering::Greater,
+            (Some(b'^'), _) => return Ordering::Less,
+            (_, Some(b'^')) => return Ordering::Greater,
+            (None, _) | (_, None) => break,
+            _ => {}
+        }
+
+        let is_num = one[0].is_ascii_digit();
+        let segment = |s: &[u8]| -> usize {
+            s.iter()
+                .position(|c| {
+                    if is_num {
+                        !c.is_ascii_digit()
+                    } else {
+                        !c.is_ascii_alphabetic()
+                    }
+                })
+                .unwrap_or(s.len())
+        };
+        let (len1, len2) = (segment(one), segment(two));
+        let (mut seg1, mut seg2) = (&one[..len1], &two[..len2]);
+        one = &one[len1..];
+        two = &two[len2..];
+
+        // Segments of different types: numeric ones are newer
+        if seg2.is_empty() {
+            return if is_num {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+
+        if is_num {
+            while seg1.first() == Some(&b'0') {
+                seg1 = &seg1[1..];
+            }
+            while seg2.first() == Some(&b'0') {
+                seg2 = &seg2[1..];
+            }
+            let by_len = seg1.len().cmp(&seg2.len());
+            if by_len.is_ne() {
+                return by_len;
+            }
+        }
+        let by_content = seg1.cmp(seg2);
+        if by_content.is_ne() {
+            return by_content;
+        }
+    }
+
+    match (one.is_empty(), two.is_empty()) {
+        (true, true) => Ordering::Equal,
+        (false, _) => Ordering::Greater,
+        (_, false) => Ordering::Less,
+    }
+}
@@ -0,0 +1,163 @@
+use std::cmp::Ordering;
+
+use nu_protocol::{ShellError, Value};
+
+use crate::{
+    config::SemverConfig,
+    custom_value::{Pep440CustomValue, SemverCustomValue},
+    deb::DebVersion,
+    go,
+    maven::MavenVersion,
+    rpm::RpmVersion,
+    version::VersionError,
+};
+
+/// A versioning scheme, i.e. a way of parsing and ordering versions.
+pub trait VersioningScheme {
+    type Version;
+
+    #[allow(clippy::result_large_err)]
+    fn parse(&self, value: &Value) -> Result<Self::Version, ShellError>;
+
+    /// The ordering of the versions, which is total so that it can be used to sort them.
+    fn compare(&self, a: &Self::Version, b: &Self::Version) -> Ordering;
+
+    /// Compare two versions on their own. This is their ordering, unless the scheme considers
+    /// distinct versions to match, e.g. RPM versions without a release.
+    fn compare_pair(&self, a: &Self::Version, b: &Self::Version) -> Ordering {
+        self.compare(a, b)
+    }
+
+    /// Compare two values, parsing them with this scheme.
+    #[allow(clippy::result_large_err)]
+    fn compare_values(&self, a: &Value, b: &Value) -> Result<Ordering, ShellError> {
+        Ok(self.compare_pair(&self.parse(a)?, &self.parse(b)?))
+    }
+}
+
+/// The versioning schemes that can be selected with `--scheme`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Scheme {
+    #[default]
+    Semver,
+    Pep440,
+    Deb,
+    Rpm,
+    Go,
+    Maven,
+}
+
+/// SemVer ordering, honoring the plugin configuration.
+pub struct SemverScheme<'a> {
+    pub config: &'a SemverConfig,
+    pub lenient: bool,
+}
+
+impl VersioningScheme for SemverScheme<'_> {
+    type Version = SemverCustomValue;
+
+    fn parse(&self, value: &Value) -> Result<Self::Version, ShellError> {
+        SemverCustomValue::try_from_value(value, self.lenient)
+    }
+
+    fn compare(&self, a: &Self::Version, b: &Self::Version) -> Ordering {
+        self.config.cmp_versions(a, b)
+    }
+}
+
+/// Python versions, following PEP 440.
+pub struct Pep440Scheme;
+
+impl VersioningScheme for Pep440Scheme {
+    type Version = Pep440CustomValue;
+
+    fn parse(&self, value: &Value) -> Result<Self::Version, ShellError> {
+        Pep440CustomValue::try_from(value)
+    }
+
+    fn compare(&self, a: &Self::Version, b: &Self::Version) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Debian package versions, ordered like `dpkg --compare-versions`.
+pub struct DebScheme;
+
+impl VersioningScheme for DebScheme {
+    type Version = DebVersion;
+
+    fn parse(&self, value: &Value) -> Result<Self::Version, ShellError> {
+        parse_string(value, DebVersion::parse)
+    }
+
+    fn compare(&self, a: &Self::Version, b: &Self::Version) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// RPM package versions, ordered like `rpmdev-vercmp`.
+pub struct RpmScheme;
+
+impl VersioningScheme for RpmScheme {
+    type Version = RpmVersion;
+
+    fn parse(&self, value: &Value) -> Result<Self::Version, ShellError> {
+        parse_string(value, RpmVersion::parse)
+    }
+
+    fn compare(&self, a: &Self::Version, b: &Self::Version) -> Ordering {
+        a.cmp(b)
+    }
+
+    fn compare_pair(&self, a: &Self::Version, b: &Self::Version) -> Ordering {
+        a.vercmp(b)
+    }
+}
+
+/// Go module versions, ordered like `golang.org/x/mod/semver`: build metadata is ignored, and
+/// invalid versions sort before valid ones.
+pub struct GoScheme;
+
+impl VersioningScheme for GoScheme {
+    type Version = Option<semver::Version>;
+
+    fn parse(&self, value: &Value) -> Result<Self::Version, ShellError> {
+        Ok(go::parse(&value.coerce_str()?).ok())
+    }
+
+    fn compare(&self, a: &Self::Version, b: &Self::Version) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => a.cmp_precedence(b),
+            _ => a.is_some().cmp(&b.is_some()),
+        }
+    }
+}
+
+/// Maven and Gradle versions, ordered like Maven's `ComparableVersion`.
+pub struct MavenScheme;
+
+impl VersioningScheme for MavenScheme {
+    type Version = MavenVersion;
+
+    fn parse(&self, value: &Value) -> Result<Self::Version, ShellError> {
+        parse_string(value, MavenVersion::parse)
+    }
+
+    fn compare(&self, a: &Self::Version, b: &Self::Version) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_string<T>(
+    value: &Value,
+    parse: impl Fn(&str) -> Result<T, VersionError>,
+) -> Result<T, ShellError> {
+    let span = value.span();
+    parse(value.coerce_str()?.as_ref()).map_err(|e| ShellError::IncorrectValue {
+        msg: e.to_string(),
+        val_span: span,
+        call_span: span,
+    })
+}
@@ -1,7 +1,12 @@
-use std::{num::ParseIntError, ops::Deref};
+use std::num::ParseIntError;
 
 use nu_protocol::{FromValue, LabeledError, ShellError, Span, Value};
 
+use crate::{
+    custom_value::SemverReqCustomValue,
+    range::{self, VersionRange},
+};
+
 pub const ALPHA: &str = "alpha";
 pub const BETA: &str = "beta";
 pub const RC: &str = "rc";
@@ -9,7 +14,45 @@ pub const RC: &str = "rc";
 #[derive(Debug, thiserror::Error)]
 pub enum VersionError {
     #[error("Invalid level {0} for pre-release {1}")]
-    InvalidLevelForPrerelease(Level, String),
+    InvalidLevelForPrerelease(String, String),
+    #[error("Pre-release label {0} is not one of the configured labels")]
+    UnknownPrereleaseLabel(String),
+    #[error("Invalid pre-release labels: {0}")]
+    InvalidPrereleaseLabels(String),
+    #[error("Invalid npm range: {0}")]
+    InvalidNpmRange(String),
+    #[error("Invalid PEP 440 version: {0}")]
+    InvalidPep440Version(String),
+    #[error("Invalid PEP 440 specifier {0}: {1}")]
+    InvalidPep440Specifier(String, String),
+    #[error("{0} has no SemVer equivalent: {1}")]
+    NoSemverEquivalent(String, String),
+    #[error("{0} has no PEP 440 equivalent: {1}")]
+    NoPep440Equivalent(String, String),
+    #[error("Invalid Debian version {0}: {1}")]
+    InvalidDebVersion(String, String),
+    #[error("Invalid RPM version {0}: {1}")]
+    InvalidRpmVersion(String, String),
+    #[error("Invalid Go version {0}: {1}")]
+    InvalidGoVersion(String, String),
+    #[error("Invalid Maven version {0}: {1}")]
+    InvalidMavenVersion(String, String),
+    #[error("Invalid Maven version range {0}: {1}")]
+    InvalidMavenRange(String, String),
+    #[error("Invalid CalVer format {0}: {1}")]
+    InvalidCalverFormat(String, String),
+    #[error("Invalid CalVer version {0}: {1}")]
+    InvalidCalver(String, String),
+    #[error("Invalid date {0}, expected YYYY-MM-DD")]
+    InvalidDate(String),
+    #[error("Can't bump {0}: {1}")]
+    CalverBump(String, String),
+    #[error("Invalid manifest {0}: {1}")]
+    InvalidManifest(String, String),
+    #[error("Invalid Cargo workspace {0}: {1}")]
+    InvalidWorkspace(String, String),
+    #[error("git error: {0}")]
+    Git(String),
     #[error("Only numerical suffixes are supported in pre-release field: {0}")]
     InvalidNumericSuffixInPrerelease(#[from] ParseIntError),
     #[error(transparent)]
@@ -31,87 +74,266 @@ pub enum Level {
     Alpha,
     Beta,
     Rc,
+    Pre,
+    Premajor,
+    Preminor,
+    Prepatch,
+    Prerelease,
     Release,
 }
 
+impl Level {
+    /// Whether this level accepts an explicit pre-release label.
+    pub fn takes_label(self) -> bool {
+        matches!(
+            self,
+            Level::Pre | Level::Premajor | Level::Preminor | Level::Prepatch | Level::Prerelease
+        )
+    }
+}
+
+/// The ordered chain of labels that pre-releases go through, e.g. alpha, beta, then rc.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct VersionValue {
-    version: semver::Version,
-    span: Span,
+pub struct PrereleaseLabels(Vec<String>);
+
+impl PrereleaseLabels {
+    pub fn new(labels: Vec<String>) -> Result<Self, VersionError> {
+        if labels.is_empty() {
+            return Err(VersionError::InvalidPrereleaseLabels(
+                "at least one label is required".into(),
+            ));
+        }
+        for (i, label) in labels.iter().enumerate() {
+            let is_identifier =
+                !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+            if !is_identifier || label.chars().all(|c| c.is_ascii_digit()) {
+                return Err(VersionError::InvalidPrereleaseLabels(format!(
+                    "{label} is not a valid non-numeric pre-release identifier"
+                )));
+            }
+            if labels[..i].contains(label) {
+                return Err(VersionError::InvalidPrereleaseLabels(format!(
+                    "{label} appears more than once"
+                )));
+            }
+        }
+        Ok(Self(labels))
+    }
+
+    pub fn first(&self) -> &str {
+        &self.0[0]
+    }
+
+    pub fn position(&self, label: &str) -> Option<usize> {
+        self.0.iter().position(|l| l == label)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
 }
 
-impl VersionValue {
-    pub fn into_value(self) -> Value {
-        Value::string(self.version.to_string(), self.span)
+impl Default for PrereleaseLabels {
+    fn default() -> Self {
+        Self(vec![ALPHA.into(), BETA.into(), RC.into()])
     }
 }
 
-impl<'a> TryFrom<&'a Value> for VersionValue {
-    type Error = ShellError;
+/// The kind of change between two versions, as reported by `semver diff`.
+///
+/// This is modelled on npm's `semver.diff`, but follows the rules of [`Level`], so that bumping a
+/// version at a given level and diffing the result against the original version reports that
+/// same level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Change {
+    Major,
+    Minor,
+    Patch,
+    Premajor,
+    Preminor,
+    Prepatch,
+    Prerelease,
+    Build,
+    None,
+}
 
-    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
-        let span = value.span();
-        let item = value.as_str()?;
+impl Change {
+    pub fn between(a: &semver::Version, b: &semver::Version) -> Self {
+        let (low, high) = match a.cmp_precedence(b) {
+            std::cmp::Ordering::Less => (a, b),
+            std::cmp::Ordering::Greater => (b, a),
+            std::cmp::Ordering::Equal if a.build != b.build => return Change::Build,
+            std::cmp::Ordering::Equal => return Change::None,
+        };
+        let same_core = (low.major, low.minor, low.patch) == (high.major, high.minor, high.patch);
+
+        if same_core && !low.pre.is_empty() && high.pre.is_empty() {
+            // Releasing a pre-release keeps its version core, which is what bumping the patch
+            // level of a pre-release does, e.g. 2.0.0-rc.1 -> 2.0.0.
+            return Change::Patch;
+        }
 
-        let version = semver::Version::parse(item).map_err(|e| ShellError::IncorrectValue {
-            msg: format!("Value is not a valid semver version: {e}"),
-            val_span: span,
-            call_span: span,
-        })?;
-        Ok(Self { version, span })
+        let pre = !high.pre.is_empty();
+        if a.major != b.major {
+            if pre { Change::Premajor } else { Change::Major }
+        } else if a.minor != b.minor {
+            if pre { Change::Preminor } else { Change::Minor }
+        } else if a.patch != b.patch {
+            if pre { Change::Prepatch } else { Change::Patch }
+        } else {
+            Change::Prerelease
+        }
     }
 }
 
-impl FromValue for VersionValue {
-    fn from_value(v: Value) -> Result<Self, ShellError> {
-        (&v).try_into()
+/// The result of coercing an arbitrary string into a SemVer version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoercedVersion {
+    pub version: semver::Version,
+    /// Text that was dropped before the version, e.g. the `v` in `v1.2.3`.
+    pub prefix: String,
+    /// Text that was dropped after the version, e.g. the `.4` in `1.2.3.4`.
+    pub suffix: String,
+    /// Components that were missing and have been set to 0.
+    pub padded: Vec<&'static str>,
+}
+
+/// Extract the best-effort SemVer version from a string, similarly to npm's `semver.coerce`.
+///
+/// The first run of up to three dot-separated numbers is used as the version core, and missing
+/// components are padded with zeros. A pre-release and/or build metadata directly following the
+/// core is kept if it is valid, anything else around the core is dropped. Returns `None` if the
+/// string doesn't contain any number.
+pub fn coerce(input: &str) -> Option<CoercedVersion> {
+    let input = input.trim();
+    if let Ok(version) = semver::Version::parse(input) {
+        return Some(CoercedVersion {
+            version,
+            prefix: String::new(),
+            suffix: String::new(),
+            padded: vec![],
+        });
     }
+
+    let start = input.find(|c: char| c.is_ascii_digit())?;
+    let mut end = start;
+    let mut components = vec![];
+    while components.len() < 3 {
+        let len = input[end..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len() - end);
+        components.push(input[end..end + len].parse::<u64>().ok()?);
+        end += len;
+
+        let rest = &input[end..];
+        if components.len() < 3
+            && rest.starts_with('.')
+            && rest[1..].starts_with(|c: char| c.is_ascii_digit())
+        {
+            end += 1;
+        } else {
+            break;
+        }
+    }
+
+    let padded = ["minor", "patch"][components.len() - 1..].to_vec();
+    components.resize(3, 0);
+    let mut version = semver::Version::new(components[0], components[1], components[2]);
+
+    let mut suffix = &input[end..];
+    if suffix.starts_with(['-', '+'])
+        && let Ok(v) = semver::Version::parse(&format!("{version}{suffix}"))
+    {
+        version = v;
+        suffix = "";
+    }
+
+    Some(CoercedVersion {
+        version,
+        prefix: input[..start].to_string(),
+        suffix: suffix.to_string(),
+        padded,
+    })
 }
 
-impl Deref for VersionValue {
-    type Target = semver::Version;
+pub fn not_coercible_error(span: Span) -> ShellError {
+    ShellError::IncorrectValue {
+        msg: "Value does not contain anything that can be coerced into a semver version".into(),
+        val_span: span,
+        call_span: span,
+    }
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.version
+/// The grammar used to parse version requirements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Dialect {
+    /// Cargo's requirements, e.g. `^1.2, <1.5`
+    #[default]
+    Cargo,
+    /// npm (node-semver) ranges, e.g. `>=1.2.0 <1.5.0 || 2.x`
+    Npm,
+}
+
+impl Dialect {
+    /// Parse a requirement into its `||` alternatives. Cargo requirements always have a single
+    /// alternative.
+    pub fn parse(self, req: &str) -> Result<Vec<semver::VersionReq>, VersionError> {
+        match self {
+            Dialect::Cargo => Ok(vec![semver::VersionReq::parse(req)?]),
+            Dialect::Npm => crate::npm::parse_range(req),
+        }
     }
 }
 
+/// A version requirement, made of one or more alternatives. A version satisfies the requirement
+/// if it satisfies any of the alternatives.
 pub struct VersionReqValue {
-    req: semver::VersionReq,
-    #[allow(dead_code)]
+    reqs: Vec<semver::VersionReq>,
     span: Span,
 }
 
 impl VersionReqValue {
-    #[allow(dead_code)]
-    pub fn into_inner(self) -> semver::VersionReq {
-        self.req
+    /// Convert a value into a version requirement, parsing strings with the given dialect.
+    #[allow(clippy::result_large_err)]
+    pub fn try_from_value(value: &Value, dialect: Dialect) -> Result<Self, ShellError> {
+        let span = value.span();
+        let SemverReqCustomValue(reqs) = SemverReqCustomValue::try_from_value(value, dialect)?;
+        Ok(Self { reqs, span })
     }
 
-    #[allow(dead_code)]
     pub fn into_value(self) -> Value {
-        Value::string(self.req.to_string(), self.span)
+        SemverReqCustomValue(self.reqs).into_value(self.span)
     }
-}
 
-impl FromValue for VersionReqValue {
-    fn from_value(v: Value) -> Result<Self, ShellError> {
-        let span = v.span();
-        let item = v.as_str()?;
+    pub fn alternatives(&self) -> &[semver::VersionReq] {
+        &self.reqs
+    }
 
-        let req = semver::VersionReq::parse(item).map_err(|e| ShellError::IncorrectValue {
-            msg: format!("Value is not a valid semver requirement: {e}"),
-            val_span: span,
-            call_span: span,
-        })?;
-        Ok(Self { req, span })
+    pub fn matches(&self, version: &semver::Version, include_prerelease: bool) -> bool {
+        self.reqs
+            .iter()
+            .any(|req| range::matches(req, version, include_prerelease))
+    }
+
+    /// The ranges of versions matched by each alternative.
+    pub fn ranges(&self) -> Vec<VersionRange> {
+        self.reqs.iter().map(VersionRange::from_req).collect()
     }
 }
 
-impl Deref for VersionReqValue {
-    type Target = semver::VersionReq;
+impl<'a> TryFrom<&'a Value> for VersionReqValue {
+    type Error = ShellError;
 
-    fn deref(&self) -> &Self::Target {
-        &self.req
+    /// Accepts either a Cargo requirement string or a `semver-req` custom value.
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        Self::try_from_value(value, Dialect::Cargo)
+    }
+}
+
+impl FromValue for VersionReqValue {
+    fn from_value(v: Value) -> Result<Self, ShellError> {
+        (&v).try_into()
     }
 }
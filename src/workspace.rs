@@ -0,0 +1,482 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml_edit::{DocumentMut, Item};
+
+use crate::{
+    manifest::{self, Manifest},
+    version::VersionError,
+};
+
+/// The kinds of dependency tables, which can also appear under `[target.'cfg(...)']`.
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// A Cargo workspace: the root manifest and the manifests of its members.
+pub struct Workspace {
+    pub root: PathBuf,
+    /// The root manifest first, then the other members.
+    manifests: Vec<Manifest>,
+    /// The indices of the members in `manifests`, including the root package, if any.
+    members: Vec<usize>,
+    /// The indices of the edited manifests.
+    edited: HashSet<usize>,
+}
+
+/// A change made to a manifest.
+pub struct Edit {
+    pub path: PathBuf,
+    /// The package of the edited manifest, if it has one.
+    pub package: Option<String>,
+    pub field: String,
+    pub from: String,
+    pub to: String,
+}
+
+impl Workspace {
+    /// Find the workspace containing the given directory, and read its members.
+    pub fn discover(dir: &Path) -> Result<Self, VersionError> {
+        let mut root_manifest = None;
+        for path in dir.ancestors().map(|d| d.join("Cargo.toml")) {
+            if path.is_file() {
+                let manifest = Manifest::open(&path)?;
+                if manifest.toml()?.contains_key("workspace") {
+                    root_manifest = Some(manifest);
+                    break;
+                }
+            }
+        }
+        let root_manifest = root_manifest
+            .ok_or_else(|| invalid(dir, "no Cargo.toml with a [workspace] section"))?;
+        let root = root_manifest
+            .path
+            .parent()
+            .expect("a manifest is in a directory")
+            .to_owned();
+
+        let doc = root_manifest.toml()?;
+        let patterns = |key| {
+            manifest::get(doc.as_item(), &["workspace", key])
+                .and_then(Item::as_array)
+                .map(|patterns| {
+                    patterns
+                        .iter()
+                        .filter_map(|p| p.as_str())
+                        .map(ToOwned::to_owned)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+        let excluded: HashSet<PathBuf> = patterns("exclude").iter().map(|p| root.join(p)).collect();
+
+        let mut members = vec![];
+        if doc.contains_key("package") {
+            members.push(0);
+        }
+        let mut manifests = vec![root_manifest];
+        for pattern in patterns("members") {
+            for dir in expand(&root, &pattern) {
+                let path = dir.join("Cargo.toml");
+                if path.is_file() && !excluded.contains(&dir) && path != manifests[0].path {
+                    members.push(manifests.len());
+                    manifests.push(Manifest::open(&path)?);
+                }
+            }
+        }
+        Ok(Self {
+            root,
+            manifests,
+            members,
+            edited: HashSet::new(),
+        })
+    }
+
+    /// Bump the version of a member, and update the requirements of the `path` dependencies on
+    /// it that don't match the new version anymore. If the member inherits its version from the
+    /// workspace, the workspace version is bumped, along with every member inheriting it.
+    pub fn bump(
+        &mut self,
+        package: &str,
+        bump: impl FnOnce(&semver::Version) -> Result<semver::Version, VersionError>,
+    ) -> Result<Vec<Edit>, VersionError> {
+        let mut index = None;
+        let mut inheriting = HashSet::new();
+        for &i in &self.members {
+            let member = &self.manifests[i];
+            let name = member.package_name()?;
+            if name.as_deref() == Some(package) {
+                index = Some(i);
+            }
+            if member.inherits_version()?
+                && let Some(name) = name
+            {
+                inheriting.insert(name);
+            }
+        }
+        let index =
+            index.ok_or_else(|| invalid(&self.root, &format!("no member named {package}")))?;
+
+        // The bumped packages, the index of the manifest holding their version, and the path of
+        // the version in that manifest. A shared version is always the one of
+        // `[workspace.package]`, even if the root package has its own version.
+        let (bumped, source, field_path) = if inheriting.contains(package) {
+            (inheriting, 0, manifest::WORKSPACE_VERSION)
+        } else {
+            let manifest = &self.manifests[index];
+            let path = manifest.resolved_version_path(&manifest.toml()?)?;
+            (HashSet::from([package.to_owned()]), index, path)
+        };
+
+        let mut edits = vec![];
+        let manifest = &mut self.manifests[source];
+        let from = manifest.version_at(field_path)?;
+        let current = semver::Version::parse(&from)
+            .map_err(|e| invalid(&manifest.path, &format!("invalid version {from}: {e}")))?;
+        let version = bump(&current)?;
+        let to = version.to_string();
+        let field = field_path.join(".");
+        manifest.set_version_at(field_path, &to)?;
+        self.edited.insert(source);
+        edits.push(Edit {
+            path: manifest.path.clone(),
+            package: manifest.package_name()?,
+            field,
+            from,
+            to,
+        });
+
+        for (i, manifest) in self.manifests.iter_mut().enumerate() {
+            let updates = update_requirements(manifest, &bumped, &version)?;
+            if !updates.is_empty() {
+                self.edited.insert(i);
+                edits.extend(updates);
+            }
+        }
+        for edit in &mut edits {
+            if let Ok(path) = edit.path.strip_prefix(&self.root) {
+                edit.path = path.to_owned();
+            }
+        }
+        Ok(edits)
+    }
+
+    /// Write the edited manifests.
+    pub fn save(&self) -> Result<(), VersionError> {
+        self.edited
+            .iter()
+            .try_for_each(|&i| self.manifests[i].save())
+    }
+}
+
+/// Update the requirements of the `path` dependencies on the bumped packages, when they don't
+/// match the new version.
+fn update_requirements(
+    manifest: &mut Manifest,
+    bumped: &HashSet<String>,
+    version: &semver::Version,
+) -> Result<Vec<Edit>, VersionError> {
+    let mut doc = manifest.toml()?;
+    let package = manifest.package_name()?;
+    let mut edits = vec![];
+
+    for table in dependency_tables(&doc) {
+        let path: Vec<&str> = table.iter().map(String::as_str).collect();
+        let Some(deps) =
+            manifest::get_mut(doc.as_item_mut(), &path).and_then(Item::as_table_like_mut)
+        else {
+            continue;
+        };
+        for (key, dep) in deps.iter_mut() {
+            let Some(dep) = dep.as_table_like_mut() else {
+                continue;
+            };
+            let name = dep
+                .get("package")
+                .and_then(Item::as_str)
+                .unwrap_or(key.get());
+            if !bumped.contains(name) || !dep.contains_key("path") {
+                continue;
+            }
+            let Some(req) = dep.get_mut("version").and_then(Item::as_value_mut) else {
+                continue;
+            };
+            let Some(from) = req.as_str().map(ToOwned::to_owned) else {
+                continue;
+            };
+            let matches = semver::VersionReq::parse(&from)
+                .map_err(|e| invalid(&manifest.path, &format!("invalid requirement {from}: {e}")))?
+                .matches(version);
+            if matches {
+                continue;
+            }
+            let to = new_requirement(&from, version);
+            manifest::replace_str(req, &to);
+            edits.push(Edit {
+                path: manifest.path.clone(),
+                package: package.clone(),
+                field: format!("{}.{}.version", table.join("."), key.get()),
+                from,
+                to,
+            });
+        }
+    }
+
+    if !edits.is_empty() {
+        manifest.set_toml(&doc);
+    }
+    Ok(edits)
+}
+
+/// The paths of the dependency tables of a manifest.
+fn dependency_tables(doc: &DocumentMut) -> Vec<Vec<String>> {
+    let mut tables: Vec<Vec<String>> = DEPENDENCY_TABLES
+        .iter()
+        .map(|table| vec![table.to_string()])
+        .collect();
+    tables.push(vec!["workspace".to_owned(), "dependencies".to_owned()]);
+    if let Some(targets) = doc.get("target").and_then(Item::as_table_like) {
+        for (target, _) in targets.iter() {
+            for table in DEPENDENCY_TABLES {
+                tables.push(vec![
+                    "target".to_owned(),
+                    target.to_owned(),
+                    table.to_owned(),
+                ]);
+            }
+        }
+    }
+    tables
+}
+
+/// A requirement on the new version, keeping the operator of single-comparator requirements
+/// when it still makes sense for the new version (e.g. `=0.3.1` becomes `=0.4.0`). Other
+/// requirements, e.g. `<0.4` or `>=0.3, <0.4`, become caret requirements.
+fn new_requirement(old: &str, version: &semver::Version) -> String {
+    let old = old.trim();
+    let operator: String = old
+        .chars()
+        .take_while(|c| !c.is_ascii_digit())
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    match operator.as_str() {
+        "" | "^" | "~" | "=" if !old.contains(',') => format!("{operator}{version}"),
+        _ => format!("^{version}"),
+    }
+}
+
+/// Expand a member pattern, e.g. `crates/*`, into the matching directories.
+fn expand(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_owned()];
+    for component in pattern.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        dirs = if component.contains(['*', '?']) {
+            dirs.iter()
+                .filter_map(|dir| fs::read_dir(dir).ok())
+                .flatten()
+                .filter_map(Result::ok)
+                .filter(|entry| {
+                    entry.file_type().is_ok_and(|t| t.is_dir())
+                        && entry
+                            .file_name()
+                            .to_str()
+                            .is_some_and(|name| glob_match(component, name))
+                })
+                .map(|entry| entry.path())
+                .collect()
+        } else {
+            dirs.iter().map(|dir| dir.join(component)).collect()
+        };
+    }
+    dirs.sort();
+    dirs
+}
+
+/// Match a file name against a pattern, where `*` matches any sequence of characters and `?` any
+/// single character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    match pattern.chars().next() {
+        None => name.is_empty(),
+        Some('*') => {
+            let rest = &pattern[1..];
+            name.char_indices()
+                .map(|(i, _)| i)
+                .chain([name.len()])
+                .any(|i| glob_match(rest, &name[i..]))
+        }
+        Some(c) => {
+            let mut chars = name.chars();
+            chars.next().is_some_and(|n| c == '?' || c == n)
+                && glob_match(&pattern[c.len_utf8()..], chars.as_str())
+        }
+    }
+}
+
+fn invalid(path: &Path, reason: &str) -> VersionError {
+    VersionError::InvalidWorkspace(path.display().to_string(), reason.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    /// Bump a package to the given version, returning the edits as `(path, field, from, to)`.
+    fn bump(dir: &TempDir, package: &str, version: &str) -> Vec<(String, String, String, String)> {
+        let mut workspace = Workspace::discover(dir.path()).unwrap();
+        let edits = workspace
+            .bump(package, |_| Ok(semver::Version::parse(version).unwrap()))
+            .unwrap();
+        workspace.save().unwrap();
+        edits
+            .into_iter()
+            .map(|e| (e.path.display().to_string(), e.field, e.from, e.to))
+            .collect()
+    }
+
+    fn edit(path: &str, field: &str, from: &str, to: &str) -> (String, String, String, String) {
+        (path.into(), field.into(), from.into(), to.into())
+    }
+
+    const APP: &str = r#"[package]
+name = "app"
+version = "1.0.0"
+
+[dependencies]
+core = { path = "../core", version = "0.3" } # keep in sync
+"#;
+
+    const CLI: &str = r#"[package]
+name = "cli"
+version = "1.0.0"
+
+[dev-dependencies]
+my-core = { package = "core", path = "../core", version = "<0.4" }
+"#;
+
+    fn glob_workspace(name: &str) -> TempDir {
+        let ws = TempDir::new(name);
+        ws.write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n",
+        );
+        ws.write(
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"core\"\nversion = \"0.3.1\"\n",
+        );
+        ws.write("crates/app/Cargo.toml", APP);
+        ws.write("crates/cli/Cargo.toml", CLI);
+        ws.write(
+            "crates/old/Cargo.toml",
+            "[package]\nname = \"old\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = { path = \"../core\", version = \"0.2\" }\n",
+        );
+        ws
+    }
+
+    #[test]
+    fn bump_glob_members() {
+        let ws = glob_workspace("workspace-glob");
+        assert_eq!(
+            bump(&ws, "core", "0.4.0"),
+            vec![
+                edit(
+                    "crates/core/Cargo.toml",
+                    "package.version",
+                    "0.3.1",
+                    "0.4.0"
+                ),
+                edit(
+                    "crates/app/Cargo.toml",
+                    "dependencies.core.version",
+                    "0.3",
+                    "0.4.0"
+                ),
+                edit(
+                    "crates/cli/Cargo.toml",
+                    "dev-dependencies.my-core.version",
+                    "<0.4",
+                    "^0.4.0"
+                ),
+            ]
+        );
+        assert_eq!(
+            ws.read("crates/core/Cargo.toml"),
+            "[package]\nname = \"core\"\nversion = \"0.4.0\"\n"
+        );
+        assert_eq!(
+            ws.read("crates/app/Cargo.toml"),
+            APP.replace(r#"version = "0.3""#, r#"version = "0.4.0""#)
+        );
+        assert_eq!(
+            ws.read("crates/cli/Cargo.toml"),
+            CLI.replace(r#"version = "<0.4""#, r#"version = "^0.4.0""#)
+        );
+        // Excluded directories aren't members
+        assert!(
+            ws.read("crates/old/Cargo.toml")
+                .contains(r#"version = "0.2""#)
+        );
+    }
+
+    #[test]
+    fn keep_matching_requirements() {
+        let ws = glob_workspace("workspace-matching");
+        assert_eq!(
+            bump(&ws, "core", "0.3.2"),
+            vec![edit(
+                "crates/core/Cargo.toml",
+                "package.version",
+                "0.3.1",
+                "0.3.2"
+            )]
+        );
+        assert_eq!(ws.read("crates/app/Cargo.toml"), APP);
+        assert_eq!(ws.read("crates/cli/Cargo.toml"), CLI);
+    }
+
+    #[test]
+    fn bump_inheriting_members() {
+        let ws = TempDir::new("workspace-inherit");
+        let root = r#"[workspace]
+members = ["core", "app"]
+
+[workspace.package]
+version = "1.2.0" # shared by all the crates
+
+[workspace.dependencies]
+core = { path = "core", version = "1.2" }
+
+[package]
+name = "root"
+version = "0.1.0"
+"#;
+        let member = |name: &str| {
+            format!("[package]\nname = \"{name}\"\nversion.workspace = true\n\n[dependencies]\n")
+        };
+        ws.write("Cargo.toml", root);
+        ws.write("core/Cargo.toml", &member("core"));
+        ws.write(
+            "app/Cargo.toml",
+            &(member("app") + "core.workspace = true\n"),
+        );
+
+        assert_eq!(
+            bump(&ws, "app", "2.0.0"),
+            vec![
+                edit("Cargo.toml", "workspace.package.version", "1.2.0", "2.0.0"),
+                edit(
+                    "Cargo.toml",
+                    "workspace.dependencies.core.version",
+                    "1.2",
+                    "2.0.0"
+                ),
+            ]
+        );
+        assert_eq!(
+            ws.read("Cargo.toml"),
+            root.replace(r#""1.2.0""#, r#""2.0.0""#)
+                .replace(r#""1.2""#, r#""2.0.0""#)
+        );
+        assert_eq!(ws.read("core/Cargo.toml"), member("core"));
+    }
+}